@@ -1,7 +1,192 @@
-#include<rust/cxx.h>;
-#include<mzdata_cxx/src/lib.rs.h>;
+#pragma once
+
+#include <cstddef>
+#include <iterator>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <utility>
+
+#include <rust/cxx.h>
+#include <mzdata_cxx/src/lib.rs.h>
 
 namespace mzdata_cpp
 {
 
-}; // namespace mzdata_cpp
+    /// Base class for failures raised by the bridge, carrying the `ErrorKind`
+    /// reported by the Rust side.
+    class Error : public std::runtime_error
+    {
+    public:
+        Error(ErrorKind kind, const std::string &message)
+            : std::runtime_error(message), kind_(kind) {}
+
+        ErrorKind kind() const noexcept { return kind_; }
+
+    private:
+        ErrorKind kind_;
+    };
+
+    class EndOfFileError : public Error
+    {
+    public:
+        explicit EndOfFileError(const std::string &message)
+            : Error(ErrorKind::EndOfFile, message) {}
+    };
+
+    class IndexOutOfRangeError : public Error
+    {
+    public:
+        explicit IndexOutOfRangeError(const std::string &message)
+            : Error(ErrorKind::IndexOutOfRange, message) {}
+    };
+
+    class NotFoundError : public Error
+    {
+    public:
+        explicit NotFoundError(const std::string &message)
+            : Error(ErrorKind::NotFound, message) {}
+    };
+
+    class IOError : public Error
+    {
+    public:
+        explicit IOError(const std::string &message)
+            : Error(ErrorKind::IOError, message) {}
+    };
+
+    class ParseError : public Error
+    {
+    public:
+        explicit ParseError(const std::string &message)
+            : Error(ErrorKind::ParseError, message) {}
+    };
+
+    class UnsupportedFormatError : public Error
+    {
+    public:
+        explicit UnsupportedFormatError(const std::string &message)
+            : Error(ErrorKind::UnsupportedFormat, message) {}
+    };
+
+    class ProcessingError : public Error
+    {
+    public:
+        explicit ProcessingError(const std::string &message)
+            : Error(ErrorKind::ProcessingError, message) {}
+    };
+
+    /// Re-throw a `rust::Error` as the matching `mzdata_cpp::Error` subclass. The kind
+    /// comes from `last_error_kind()`, so call this before making another bridged call.
+    [[noreturn]] inline void rethrow(const rust::Error &err)
+    {
+        std::string message(err.what());
+        switch (last_error_kind())
+        {
+        case ErrorKind::EndOfFile:
+            throw EndOfFileError(message);
+        case ErrorKind::IndexOutOfRange:
+            throw IndexOutOfRangeError(message);
+        case ErrorKind::NotFound:
+            throw NotFoundError(message);
+        case ErrorKind::IOError:
+            throw IOError(message);
+        case ErrorKind::ParseError:
+            throw ParseError(message);
+        case ErrorKind::UnsupportedFormat:
+            throw UnsupportedFormatError(message);
+        case ErrorKind::ProcessingError:
+            throw ProcessingError(message);
+        default:
+            throw Error(ErrorKind::Unknown, message);
+        }
+    }
+
+    /// Invoke `f`, translating any `rust::Error` it throws with `rethrow`.
+    ///
+    /// ```cpp
+    /// auto spec = mzdata_cpp::checked([&] { return reader->get_by_id(id); });
+    /// ```
+    template <typename F>
+    decltype(auto) checked(F &&f)
+    {
+        try
+        {
+            return std::forward<F>(f)();
+        }
+        catch (const rust::Error &err)
+        {
+            rethrow(err);
+        }
+    }
+
+    /// An input iterator over the items of a reader with `has_next` and `next`
+    /// methods. A default constructed iterator marks the end of the stream.
+    template <typename Reader, typename Item>
+    class ReaderIterator
+    {
+    public:
+        using iterator_category = std::input_iterator_tag;
+        using value_type = Item;
+        using difference_type = std::ptrdiff_t;
+        using pointer = Item *;
+        using reference = Item &;
+
+        ReaderIterator() = default;
+
+        explicit ReaderIterator(Reader &reader) : reader_(&reader)
+        {
+            advance();
+        }
+
+        reference operator*() const { return **current_; }
+        pointer operator->() const { return &**current_; }
+
+        ReaderIterator &operator++()
+        {
+            advance();
+            return *this;
+        }
+
+        void operator++(int) { advance(); }
+
+        bool operator==(const ReaderIterator &other) const
+        {
+            return reader_ == other.reader_;
+        }
+
+        bool operator!=(const ReaderIterator &other) const
+        {
+            return !(*this == other);
+        }
+
+    private:
+        void advance()
+        {
+            if (reader_ != nullptr && reader_->has_next())
+            {
+                current_.emplace(reader_->next());
+            }
+            else
+            {
+                current_.reset();
+                reader_ = nullptr;
+            }
+        }
+
+        Reader *reader_ = nullptr;
+        mutable std::optional<rust::Box<Item>> current_;
+    };
+
+    using SpectrumIterator = ReaderIterator<MZReader, Spectrum>;
+    using IonMobilityFrameIterator = ReaderIterator<IMMZReader, IonMobilityFrame>;
+
+    /// Iterate over the remaining spectra of a reader with `for (auto &spec : *reader)`.
+    inline SpectrumIterator begin(MZReader &reader) { return SpectrumIterator(reader); }
+    inline SpectrumIterator end(MZReader &) { return SpectrumIterator(); }
+
+    /// Iterate over the remaining frames of a reader with `for (auto &frame : *reader)`.
+    inline IonMobilityFrameIterator begin(IMMZReader &reader) { return IonMobilityFrameIterator(reader); }
+    inline IonMobilityFrameIterator end(IMMZReader &) { return IonMobilityFrameIterator(); }
+
+} // namespace mzdata_cpp
@@ -1,21 +1,53 @@
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 use std::pin::Pin;
 
+use mzdata::curie;
+use mzdata::mzpeaks::{CentroidPeak, DeconvolutedPeak, PeakSet, Tolerance};
+use mzdata::mzsignal::average::average_signal;
+use mzdata::mzsignal::{ArrayPair, PeakFitType, PeakPicker};
 use mzdata::prelude::*;
 
+use mzdata::io::mgf::MGFParserState;
+use mzdata::io::mzml::{MzMLParserState, MzMLWriterState};
+use mzdata::io::{
+    DetailLevel, IntoIonMobilityFrameSourceError, IonMobilityFrameAccessError,
+    MassSpectrometryFormat as MassSpectrometryFormatImpl, SpectrumAccessError,
+};
+use mzdata::meta::{
+    Component as ComponentImpl, ComponentType as ComponentTypeImpl,
+    DataProcessing as DataProcessingImpl, DissociationEnergyTerm, DissociationMethodTerm,
+    FileMetadataConfig, InstrumentConfiguration as InstrumentConfigurationImpl,
+    ProcessingMethod as ProcessingMethodImpl, Sample as SampleImpl, Software as SoftwareImpl,
+    SourceFile as SourceFileImpl,
+};
 use mzdata::params::{
     ControlledVocabulary as ControlledVocabularyImpl, Param as ParamImpl, ParamValueParseError,
-    CURIE as CURIEImpl,
+    Unit, Value, CURIE as CURIEImpl,
+};
+use mzdata::spectrum::bindata::{
+    ArrayRetrievalError, ArrayType as ArrayTypeImpl,
+    BinaryCompressionType as BinaryCompressionTypeImpl,
+    BinaryDataArrayType as BinaryDataArrayTypeImpl, DataArray as DataArrayImpl,
 };
 use mzdata::spectrum::{
-    Acquisition as AcquisitionImpl, HasIonMobility, IsolationWindow as IsolationWindowImpl, MultiLayerIonMobilityFrame as IonMobilityFrameImpl, Precursor as PrecursorImpl, ScanEvent as ScanEventImpl, SelectedIon as SelectedIonImpl, Spectrum as SpectrumImpl
+    Acquisition as AcquisitionImpl, Activation as ActivationImpl, BinaryArrayMap,
+    Chromatogram as ChromatogramImpl, ChromatogramLike, ChromatogramType as ChromatogramTypeImpl,
+    HasIonMobility, IsolationWindow as IsolationWindowImpl,
+    IsolationWindowState as IsolationWindowStateImpl,
+    MultiLayerIonMobilityFrame as IonMobilityFrameImpl, Precursor as PrecursorImpl,
+    RefPeakDataLevel, ScanEvent as ScanEventImpl, ScanPolarity as ScanPolarityImpl,
+    SelectedIon as SelectedIonImpl, SignalContinuity as SignalContinuityImpl,
+    Spectrum as SpectrumImpl, SpectrumConversionError, SpectrumDescription,
+    SpectrumProcessingError, SpectrumSummary,
 };
 
 use cxx::{CxxString, CxxVector};
 
 macro_rules! result_bool {
     ($op:expr, $out:ident) => {
-        if let Some(val) = $op.ok() {
+        if let Ok(val) = $op {
             *$out = val;
             return true;
         } else {
@@ -36,25 +68,23 @@ macro_rules! option_bool {
 }
 
 macro_rules! option_box_or_err {
-    ($op:expr, $err:literal) => {
+    ($op:expr, $kind:ident, $err:expr) => {
         match $op {
             Some(item) => Ok(Box::new(item)),
-            None => Err($err.into()),
+            None => Err(Error::new(ffi::ErrorKind::$kind, $err).into()),
         }
     };
     ($op:expr, $err:expr) => {
-        match $op {
-            Some(item) => Ok(Box::new(item)),
-            None => Err($err),
-        }
+        option_box_or_err!($op, NotFound, $err)
     };
 }
 
 macro_rules! param_methods {
     () => {
-        pub fn param(&self, index: usize) -> Result<Box<Param>, String> {
+        pub fn param(&self, index: usize) -> Result<Box<Param>, BridgeError> {
             option_box_or_err!(
                 self.0.params().get(index).cloned().map(Param),
+                IndexOutOfRange,
                 "Parameter not found"
             )
         }
@@ -63,7 +93,7 @@ macro_rules! param_methods {
             self.0.params().iter().cloned().map(|p| Param(p)).collect()
         }
 
-        pub fn get_param_by_curie(&self, curie: &ffi::CURIE) -> Result<Box<Param>, String> {
+        pub fn get_param_by_curie(&self, curie: &ffi::CURIE) -> Result<Box<Param>, BridgeError> {
             let params = self.0.params();
             if let Some(val) = params
                 .get_param_by_curie(&(*curie).into())
@@ -71,580 +101,3317 @@ macro_rules! param_methods {
             {
                 Ok(Box::new(val))
             } else {
-                Err(format!("{} not found", CURIEImpl::from(*curie)))
+                Err(Error::new(
+                    ffi::ErrorKind::NotFound,
+                    format!("{} not found", CURIEImpl::from(*curie)),
+                )
+                .into())
             }
         }
     };
 }
 
-pub struct MZReader(mzdata::MZReader<std::fs::File>);
-
-impl MZReader {
-    pub fn open(path: &str) -> io::Result<Box<Self>> {
-        mzdata::MZReader::open_path(path)
-            .inspect_err(|e| eprintln!("Open failed: {e}"))
-            .map(|this| Box::new(Self(this)))
-    }
-
-    pub fn next(&mut self) -> Result<Box<Spectrum>, &'static str> {
-        let spec = self.0.next().map(Spectrum);
-
-        option_box_or_err!(spec, "Failed to read next spectrum")
-    }
-
-    pub fn get_by_index(&mut self, index: usize) -> Result<Box<Spectrum>, String> {
-        option_box_or_err!(
-            self.0.get_spectrum_by_index(index).map(Spectrum),
-            format!("index {index} not found")
-        )
-    }
+macro_rules! array_methods {
+    () => {
+        pub fn array_count(&self) -> usize {
+            self.raw_arrays()
+                .map(|arrays| arrays.len())
+                .unwrap_or_default()
+        }
 
-    pub fn size(&self) -> usize {
-        self.0.len()
-    }
+        /// Get the `index`-th data array, ordered by array type
+        pub fn array(&self, index: usize) -> Result<Box<DataArray<'_>>, BridgeError> {
+            option_box_or_err!(
+                self.raw_arrays()
+                    .and_then(|arrays| sorted_arrays(arrays).into_iter().nth(index))
+                    .map(DataArray),
+                IndexOutOfRange,
+                format!("array {index} not found")
+            )
+        }
 
-    pub fn has_ion_mobility_dimension(&mut self) -> bool {
-        matches!(self.0.has_ion_mobility().unwrap_or_default(), HasIonMobility::Dimension)
-    }
+        pub fn get_array(
+            &self,
+            array_type: ffi::ArrayType,
+        ) -> Result<Box<DataArray<'_>>, BridgeError> {
+            option_box_or_err!(
+                ArrayTypeImpl::try_from(array_type)
+                    .ok()
+                    .and_then(|key| self.raw_arrays().and_then(|arrays| arrays.get(&key)))
+                    .map(DataArray),
+                format!("{array_type:?} not found")
+            )
+        }
 
-    pub fn into_frame_reader(self: Box<Self>) -> Result<Box<IMMZReader>, mzdata::io::IntoIonMobilityFrameSourceError> {
-        Ok(Box::new(IMMZReader(self.0.try_into_frame_source()?)))
-    }
+        /// Get a data array by name, which is the only way to reach non-standard arrays
+        pub fn get_array_by_name(&self, name: &str) -> Result<Box<DataArray<'_>>, BridgeError> {
+            option_box_or_err!(
+                self.raw_arrays()
+                    .and_then(|arrays| {
+                        sorted_arrays(arrays)
+                            .into_iter()
+                            .find(|array| DataArray(array).name() == name)
+                    })
+                    .map(DataArray),
+                format!("array {name} not found")
+            )
+        }
+    };
 }
 
-pub fn open(path: &str) -> io::Result<Box<MZReader>> {
-    MZReader::open(path)
-}
+macro_rules! summary_methods {
+    () => {
+        /// The total ion current, from the source file when recorded there
+        pub fn tic(&self) -> f32 {
+            summary_param(self.0.params(), curie!(MS:1000285))
+                .map(|val| val as f32)
+                .unwrap_or_else(|| self.summary().tic)
+        }
 
-pub struct IMMZReader(mzdata::io::IMMZReaderType<std::fs::File>);
+        pub fn base_peak_mz(&self) -> f64 {
+            summary_param(self.0.params(), curie!(MS:1000504))
+                .unwrap_or_else(|| self.summary().base_peak.mz)
+        }
 
-impl IMMZReader {
-    pub fn open(path: &str) -> io::Result<Box<Self>> {
-        mzdata::MZReader::open_path(path)
-            .inspect_err(|e| eprintln!("Open failed: {e}"))
-            .map(|this| this.into_frame_source())
-            .map(|this| Box::new(Self(this)))
-    }
+        pub fn base_peak_intensity(&self) -> f32 {
+            summary_param(self.0.params(), curie!(MS:1000505))
+                .map(|val| val as f32)
+                .unwrap_or_else(|| self.summary().base_peak.intensity)
+        }
 
-    pub fn next(&mut self) -> Result<Box<IonMobilityFrame>, &'static str> {
-        let spec = self.0.next().map(IonMobilityFrame);
+        pub fn lowest_mz(&self) -> f64 {
+            summary_param(self.0.params(), curie!(MS:1000528))
+                .unwrap_or_else(|| self.summary().mz_range.0)
+        }
 
-        option_box_or_err!(spec, "Failed to read next frame")
-    }
+        pub fn highest_mz(&self) -> f64 {
+            summary_param(self.0.params(), curie!(MS:1000527))
+                .unwrap_or_else(|| self.summary().mz_range.1)
+        }
 
-    pub fn get_by_index(&mut self, index: usize) -> Result<Box<IonMobilityFrame>, String> {
-        option_box_or_err!(
-            self.0.get_frame_by_index(index).map(IonMobilityFrame),
-            format!("index {index} not found")
-        )
-    }
+        pub fn peak_count(&self) -> usize {
+            self.summary().count
+        }
+    };
+}
 
-    pub fn size(&self) -> usize {
-        self.0.len()
+fn to_tolerance(value: f64, unit: ffi::ToleranceUnit) -> Tolerance {
+    match unit {
+        ffi::ToleranceUnit::Da => Tolerance::Da(value),
+        _ => Tolerance::PPM(value),
     }
 }
 
-pub fn open_im(path: &str) -> io::Result<Box<IMMZReader>> {
-    IMMZReader::open(path)
+fn summary_param(params: &[ParamImpl], curie: CURIEImpl) -> Option<f64> {
+    params
+        .get_param_by_curie(&curie)
+        .and_then(|param| param.to_f64().ok())
 }
 
+/// The params read by `summary_methods!` before falling back to the signal itself
+const SUMMARY_PARAMS: [CURIEImpl; 5] = [
+    curie!(MS:1000285),
+    curie!(MS:1000504),
+    curie!(MS:1000505),
+    curie!(MS:1000528),
+    curie!(MS:1000527),
+];
+
+/// The most cells `binned_signal` and `to_spectrum` will allocate for an intensity grid,
+/// 1 GiB of `f32` values
+const MAX_GRID_CELLS: usize = 1 << 28;
+
+/// The number of bins of `width` needed to cover `low..=high`
+fn bin_count(low: f64, high: f64, width: f64) -> usize {
+    (((high - low) / width) as usize).saturating_add(1)
+}
 
-#[derive(Debug, Clone)]
-pub struct SelectedIon(SelectedIonImpl);
-
-impl SelectedIon {
-    param_methods!();
+/// The number of cells in a grid with these dimensions, failing when it would exceed
+/// `MAX_GRID_CELLS`
+fn grid_cells(counts: &[usize]) -> Result<usize, Error> {
+    counts
+        .iter()
+        .try_fold(1usize, |total, count| total.checked_mul(*count))
+        .filter(|cells| *cells <= MAX_GRID_CELLS)
+        .ok_or_else(|| {
+            Error::new(
+                ffi::ErrorKind::ProcessingError,
+                format!("A grid of {counts:?} bins exceeds the limit of {MAX_GRID_CELLS} cells"),
+            )
+        })
 }
 
-impl IonProperties for SelectedIon {
-    #[inline]
-    fn mz(&self) -> f64 {
-        <SelectedIonImpl as IonProperties>::mz(&self.0)
+/// Decode `array_type` in place and borrow its values, failing rather than converting an
+/// array stored as something other than `dtype`
+fn view_array<'a, T: Clone>(
+    arrays: &'a mut BinaryArrayMap,
+    array_type: &ArrayTypeImpl,
+    dtype: BinaryDataArrayTypeImpl,
+) -> Result<&'a [T], Error> {
+    let array = arrays
+        .get_mut(array_type)
+        .ok_or(ArrayRetrievalError::NotFound(array_type.clone()))?;
+    array.decode_and_store()?;
+    if array.dtype != dtype {
+        return Err(Error::new(
+            ffi::ErrorKind::ProcessingError,
+            format!("The {array_type} is stored as {}, not {dtype}", array.dtype),
+        ));
     }
+    Ok(array.coerce_mut()?)
+}
 
-    #[inline]
-    fn neutral_mass(&self) -> f64 {
-        <SelectedIonImpl as IonProperties>::neutral_mass(&self.0)
-    }
+/// Sum the intensities of the m/z sorted signal within `tolerance` of `mz`
+fn window_sum(mzs: &[f64], intensities: &[f32], mz: f64, tolerance: Tolerance) -> f32 {
+    let (low, high) = tolerance.bounds(mz);
+    let start = mzs.partition_point(|mz| *mz < low);
+    let end = mzs.partition_point(|mz| *mz <= high);
+    intensities
+        .get(start..end)
+        .map(|vals| vals.iter().sum())
+        .unwrap_or_default()
+}
 
-    #[inline]
-    fn charge(&self) -> Option<i32> {
-        <SelectedIonImpl as IonProperties>::charge(&self.0)
+/// Merge centroided signal from several arrays, summing points closer than `mz_tolerance`
+/// into one point at their intensity-weighted m/z
+fn merge_centroids(pairs: &[ArrayPair<'_>], mz_tolerance: f64) -> (Vec<f64>, Vec<f32>) {
+    let mut points: Vec<(f64, f32)> = pairs
+        .iter()
+        .flat_map(|pair| {
+            pair.mz_array
+                .iter()
+                .copied()
+                .zip(pair.intensity_array.iter().copied())
+        })
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut mzs: Vec<f64> = Vec::new();
+    let mut intensities: Vec<f32> = Vec::new();
+    for (mz, int) in points {
+        match (mzs.last_mut(), intensities.last_mut()) {
+            (Some(last_mz), Some(last_int)) if mz - *last_mz <= mz_tolerance => {
+                let total = *last_int + int;
+                if total > 0.0 {
+                    *last_mz = (*last_mz * *last_int as f64 + mz * int as f64) / total as f64;
+                }
+                *last_int = total;
+            }
+            _ => {
+                mzs.push(mz);
+                intensities.push(int);
+            }
+        }
     }
+    (mzs, intensities)
 }
 
 #[derive(Debug, Clone)]
-pub struct Precursor<'a>(&'a PrecursorImpl);
+pub struct Error {
+    kind: ffi::ErrorKind,
+    message: String,
+}
 
-impl Precursor<'_> {
-    pub fn selected_mz(&self, value: &mut f64) -> bool {
-        option_bool!(self.0.ions.first().map(|i| i.mz), value)
+impl Error {
+    pub fn new(kind: ffi::ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
     }
 
-    pub fn selected_charge(&self, value: &mut i32) -> bool {
-        option_bool!(self.0.ions.first().and_then(|i| i.charge), value)
+    pub fn kind(&self) -> ffi::ErrorKind {
+        self.kind
     }
 
-    pub fn selected_ion_mobility(&self, value: &mut f64) -> bool {
-        option_bool!(self.0.ions.first().and_then(|i| i.ion_mobility()), value)
+    pub fn message(&self) -> &str {
+        &self.message
     }
+}
 
-    pub fn isolation_window(&self) -> Result<Box<IsolationWindow>, &'static str> {
-        match self.0.isolation_window.flags {
-            mzdata::spectrum::IsolationWindowState::Unknown => Err("No isolation window found"),
-            _ => Ok(Box::new(IsolationWindow(self.0.isolation_window.clone()))),
-        }
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
     }
+}
 
-    pub fn activation_energy(&self, value: &mut f32) -> bool {
-        *value = self.0.activation.energy;
-        true
-    }
+impl std::error::Error for Error {}
 
-    pub fn activation_method_is_combined(&self) -> bool {
-        self.0.activation.is_combined()
-    }
+/// The error returned by bridged functions. A `rust::Error` only carries the message, so
+/// converting an [`Error`] into this records its kind for [`last_error_kind`].
+#[derive(Debug)]
+pub struct BridgeError(Error);
 
-    pub fn activation_methods(&self) -> Vec<ffi::CURIE> {
-        self.0
-            .activation
-            .methods()
-            .iter()
-            .map(|method| {
-                let acc = method.accession();
-                let cv = method.controlled_vocabulary();
-                let c = ffi::CURIE::from(CURIEImpl::new(cv, acc));
-                c
-            })
-            .collect()
+impl<E: Into<Error>> From<E> for BridgeError {
+    fn from(value: E) -> Self {
+        let error = value.into();
+        LAST_ERROR_KIND.with(|kind| kind.set(error.kind));
+        Self(error)
     }
+}
 
-    pub fn activation_method(&self, value: &mut ffi::CURIE) -> bool {
-        if let Some(method) = self.0.activation.method() {
-            let acc = method.accession();
-            let cv = method.controlled_vocabulary();
-            *value = ffi::CURIE::from(CURIEImpl::new(cv, acc));
-            true
-        } else {
-            false
-        }
+impl std::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Acquisition<'a>(&'a AcquisitionImpl);
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        let kind = match value.kind() {
+            io::ErrorKind::Unsupported => ffi::ErrorKind::UnsupportedFormat,
+            io::ErrorKind::InvalidData => ffi::ErrorKind::ParseError,
+            _ => ffi::ErrorKind::IOError,
+        };
+        Self::new(kind, value.to_string())
+    }
+}
 
-impl<'a> Acquisition<'a> {
-    pub fn first_scan(&self) -> Result<Box<ScanEvent<'_>>, &'static str> {
-        self.0
-            .first_scan()
-            .map(|s| Box::new(ScanEvent(s)))
-            .ok_or("Scan not found")
+impl From<SpectrumAccessError> for Error {
+    fn from(value: SpectrumAccessError) -> Self {
+        let kind = match &value {
+            SpectrumAccessError::SpectrumIndexNotFound(_) => ffi::ErrorKind::IndexOutOfRange,
+            SpectrumAccessError::IOError(_) => ffi::ErrorKind::IOError,
+            _ => ffi::ErrorKind::NotFound,
+        };
+        Self::new(kind, value.to_string())
     }
+}
 
-    pub fn scan(&self, index: usize) -> Result<Box<ScanEvent<'_>>, &'static str> {
-        self.0
-            .scans
-            .get(index)
-            .map(|s| Box::new(ScanEvent(s)))
-            .ok_or("Scan not found")
+impl From<IonMobilityFrameAccessError> for Error {
+    fn from(value: IonMobilityFrameAccessError) -> Self {
+        let kind = match &value {
+            IonMobilityFrameAccessError::FrameIndexNotFound(_) => ffi::ErrorKind::IndexOutOfRange,
+            IonMobilityFrameAccessError::IOError(_) => ffi::ErrorKind::IOError,
+            _ => ffi::ErrorKind::NotFound,
+        };
+        Self::new(kind, value.to_string())
     }
+}
 
-    pub fn instrument_configuration_ids(&self) -> Vec<u32> {
-        self.0.instrument_configuration_ids()
+impl From<IntoIonMobilityFrameSourceError> for Error {
+    fn from(value: IntoIonMobilityFrameSourceError) -> Self {
+        Self::new(ffi::ErrorKind::UnsupportedFormat, value.to_string())
     }
+}
 
-    pub fn start_time(&self) -> f64 {
-        self.0.start_time()
+impl From<ArrayRetrievalError> for Error {
+    fn from(value: ArrayRetrievalError) -> Self {
+        let kind = match &value {
+            ArrayRetrievalError::NotFound(_) => ffi::ErrorKind::NotFound,
+            _ => ffi::ErrorKind::ParseError,
+        };
+        Self::new(kind, value.to_string())
     }
+}
 
-    pub fn len(&self) -> usize {
-        self.0.len()
+impl From<SpectrumProcessingError> for Error {
+    fn from(value: SpectrumProcessingError) -> Self {
+        match value {
+            SpectrumProcessingError::ArrayRetrievalError(err) => err.into(),
+            SpectrumProcessingError::SpectrumConversionError(
+                SpectrumConversionError::NoPeakData,
+            ) => Self::new(ffi::ErrorKind::NotFound, value.to_string()),
+            _ => Self::new(ffi::ErrorKind::ProcessingError, value.to_string()),
+        }
     }
+}
 
-    param_methods!();
+thread_local! {
+    static LAST_ERROR_KIND: Cell<ffi::ErrorKind> = const { Cell::new(ffi::ErrorKind::Unknown) };
 }
 
-#[derive(Debug, Clone)]
-pub struct ScanEvent<'a>(&'a ScanEventImpl);
+/// The kind of the last [`BridgeError`] created on this thread. Read it straight after
+/// catching a `rust::Error`, before another bridged call can fail and replace it.
+pub fn last_error_kind() -> ffi::ErrorKind {
+    LAST_ERROR_KIND.with(|kind| kind.get())
+}
 
-impl<'a> ScanEvent<'a> {
-    param_methods!();
+/// A spectrum reader, holding at most one spectrum read ahead by [`MZReader::has_next`]
+pub struct MZReader(mzdata::MZReader<std::fs::File>, Option<SpectrumImpl>);
 
-    pub fn start_time(&self) -> f64 {
-        self.0.start_time
+impl MZReader {
+    pub fn open(path: &str) -> Result<Box<Self>, BridgeError> {
+        let reader = mzdata::MZReader::open_path(path)?;
+        Ok(Box::new(Self(reader, None)))
     }
 
-    pub fn injection_time(&self) -> f32 {
-        self.0.injection_time
+    /// Read the next spectrum, returning `Ok(None)` at the end of the stream and
+    /// an error if the underlying parser failed.
+    fn read_next(&mut self) -> Result<Option<SpectrumImpl>, Error> {
+        if let Some(spec) = self.1.take() {
+            return Ok(Some(spec));
+        }
+        if let Some(spec) = self.0.next() {
+            return Ok(Some(spec));
+        }
+        let failed = match &self.0 {
+            mzdata::MZReader::MzML(reader) => reader.state == MzMLParserState::ParserError,
+            mzdata::MZReader::MGF(reader) => reader.state == MGFParserState::Error,
+            _ => false,
+        };
+        if failed {
+            Err(Error::new(
+                ffi::ErrorKind::ParseError,
+                "Failed to parse next spectrum",
+            ))
+        } else {
+            Ok(None)
+        }
     }
 
-    pub fn instrument_configuration_id(&self) -> u32 {
-        self.0.instrument_configuration_id
-    }
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Box<Spectrum>, BridgeError> {
+        let spec = self.read_next()?.map(Spectrum);
 
-    pub fn has_ion_mobility(&self) -> bool {
-        self.0.has_ion_mobility()
+        option_box_or_err!(spec, EndOfFile, "Failed to read next spectrum")
     }
 
-    pub fn ion_mobility(&self, value: &mut f64) -> bool {
-        if let Some(val) = self.0.ion_mobility() {
-            *value = val;
-            true
-        } else {
-            false
+    pub fn has_next(&mut self) -> Result<bool, BridgeError> {
+        if self.1.is_none() {
+            self.1 = self.read_next()?;
         }
+        Ok(self.1.is_some())
     }
 
-    pub fn filter_string(&self, mut out: Pin<&mut CxxString>) -> bool {
-        self.0.filter_string().map(|s| {
-            out.as_mut().clear();
-            out.as_mut().push_str(&s);
-            true
-        }).unwrap_or_default()
+    pub fn get_by_index(&mut self, index: usize) -> Result<Box<Spectrum>, BridgeError> {
+        if index >= self.0.len() {
+            return Err(Error::new(
+                ffi::ErrorKind::IndexOutOfRange,
+                format!("index {index} not found"),
+            )
+            .into());
+        }
+        option_box_or_err!(
+            self.0.get_spectrum_by_index(index).map(Spectrum),
+            ParseError,
+            format!("failed to read spectrum at index {index}")
+        )
     }
 
-    pub fn scan_configuration(&self, mut out: Pin<&mut CxxString>) -> bool {
-        if let Some(val) = self.0.scan_configuration() {
-            out.as_mut().push_str(&val.to_string());
-            true
-        } else {
-            false
-        }
+    pub fn get_by_id(&mut self, id: &str) -> Result<Box<Spectrum>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_spectrum_by_id(id).map(Spectrum),
+            format!("id {id} not found")
+        )
     }
 
+    pub fn get_by_time(&mut self, time: f64) -> Result<Box<Spectrum>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_spectrum_by_time(time).map(Spectrum),
+            format!("no spectrum found at time {time}")
+        )
+    }
 
-}
+    /// Fetch the spectrum `spectrum`'s precursor was selected from, falling back to the
+    /// nearest preceding spectrum of a lower MS level when it has no usable reference
+    pub fn get_parent(&mut self, spectrum: &Spectrum) -> Result<Box<Spectrum>, BridgeError> {
+        let parent = spectrum
+            .0
+            .precursor()
+            .and_then(|prec| prec.precursor_id.as_deref())
+            .and_then(|id| self.0.get_spectrum_by_id(id));
+        if let Some(parent) = parent {
+            return Ok(Box::new(Spectrum(parent)));
+        }
 
-#[derive(Debug, Clone)]
-pub struct Spectrum(SpectrumImpl);
+        let not_found = || {
+            Error::new(
+                ffi::ErrorKind::NotFound,
+                format!("no parent spectrum found for {}", spectrum.0.id()),
+            )
+        };
+        let ms_level = spectrum.0.ms_level();
+        if ms_level <= 1 {
+            return Err(not_found().into());
+        }
 
-impl Spectrum {
-    pub fn id(&self) -> &str {
-        self.0.id()
+        let detail_level = *self.0.detail_level();
+        self.0.set_detail_level(DetailLevel::MetadataOnly);
+        let parent_index = (0..spectrum.0.index().min(self.0.len()))
+            .rev()
+            .find(|index| {
+                self.0
+                    .get_spectrum_by_index(*index)
+                    .is_some_and(|candidate| candidate.ms_level() < ms_level)
+            });
+        self.0.set_detail_level(detail_level);
+
+        Ok(parent_index
+            .and_then(|index| self.0.get_spectrum_by_index(index))
+            .map(|parent| Box::new(Spectrum(parent)))
+            .ok_or_else(not_found)?)
     }
 
-    pub fn index(&self) -> usize {
-        self.0.index()
+    pub fn start_from_index(&mut self, index: usize) -> Result<(), BridgeError> {
+        self.0.start_from_index(index)?;
+        self.1 = None;
+        Ok(())
     }
 
-    pub fn start_time(&self) -> f64 {
-        self.0.start_time()
+    pub fn start_from_id(&mut self, id: &str) -> Result<(), BridgeError> {
+        self.0.start_from_id(id)?;
+        self.1 = None;
+        Ok(())
     }
 
-    pub fn ms_level(&self) -> u8 {
-        self.0.ms_level()
+    pub fn start_from_time(&mut self, time: f64) -> Result<(), BridgeError> {
+        self.0.start_from_time(time)?;
+        self.1 = None;
+        Ok(())
     }
 
-    pub fn is_profile(&self) -> bool {
-        matches!(
-            self.0.signal_continuity(),
-            mzdata::spectrum::SignalContinuity::Profile
-        )
+    pub fn reset(&mut self) {
+        self.0.reset();
+        self.1 = None;
     }
 
-    pub fn mzs_into(&self, mut container: Pin<&mut CxxVector<f64>>) {
-        for pt in self.0.peaks().iter() {
-            container.as_mut().push(pt.mz)
-        }
+    pub fn metadata(&self) -> Box<RunMetadata> {
+        Box::new(RunMetadata(FileMetadataConfig::from(&self.0)))
     }
 
-    pub fn intensities_into(&self, mut container: Pin<&mut CxxVector<f32>>) {
-        for pt in self.0.peaks().iter() {
-            container.as_mut().push(pt.intensity)
+    /// Count the chromatograms from the chromatogram index where the format has one.
+    /// Otherwise every chromatogram's metadata is read.
+    pub fn chromatogram_count(&mut self) -> usize {
+        match &self.0 {
+            mzdata::MZReader::MzML(reader) => reader.chromatogram_index.len(),
+            mzdata::MZReader::MGF(_) => 0,
+            _ => {
+                let detail_level = *self.0.detail_level();
+                self.0.set_detail_level(DetailLevel::MetadataOnly);
+                let count = self.0.iter_chromatograms().count();
+                self.0.set_detail_level(detail_level);
+                count
+            }
         }
     }
 
-    pub fn signal_into(
-        &self,
-        mut mzs_container: Pin<&mut CxxVector<f64>>,
-        mut intensities_container: Pin<&mut CxxVector<f32>>,
-    ) {
-        for pt in self.0.peaks().iter() {
-            mzs_container.as_mut().push(pt.mz);
-            intensities_container.as_mut().push(pt.intensity);
-        }
+    pub fn get_chromatogram_by_index(
+        &mut self,
+        index: usize,
+    ) -> Result<Box<Chromatogram>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_chromatogram_by_index(index).map(Chromatogram),
+            IndexOutOfRange,
+            format!("chromatogram index {index} not found")
+        )
     }
 
-    pub fn precursor(&self) -> Result<Box<Precursor<'_>>, String> {
-        option_box_or_err!(self.0.precursor().map(Precursor), "No precursor found")
+    pub fn get_chromatogram_by_id(&mut self, id: &str) -> Result<Box<Chromatogram>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_chromatogram_by_id(id).map(Chromatogram),
+            format!("chromatogram id {id} not found")
+        )
     }
 
-    pub fn acquisition(&self) -> Box<Acquisition<'_>> {
-        Box::new(Acquisition(self.0.acquisition()))
+    pub fn iter_chromatograms(&mut self) -> Box<ChromatogramIterator<'_>> {
+        Box::new(ChromatogramIterator(self.0.iter_chromatograms()))
     }
 
-    param_methods!();
-}
-
-#[derive(Debug, Clone)]
-pub struct IonMobilityFrame(IonMobilityFrameImpl);
+    /// Scan the precursor isolation windows of the whole file to find its DIA window
+    /// scheme. Only spectrum metadata is read, and the reader is reset afterwards.
+    pub fn dia_window_scheme(&mut self) -> Result<ffi::DIAWindowScheme, BridgeError> {
+        let detail_level = *self.0.detail_level();
+        self.0.set_detail_level(DetailLevel::MetadataOnly);
+        self.reset();
+
+        let mut windows = BTreeMap::new();
+        // Cycles start at an MS1 spectrum, so MSn spectra before the first are not counted
+        let mut cycles: Vec<usize> = Vec::new();
+        let result = loop {
+            let spec = match self.read_next() {
+                Ok(Some(spec)) => spec,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            if spec.ms_level() == 1 {
+                cycles.push(0);
+                continue;
+            }
+            let Some(window) = spec
+                .precursor()
+                .map(|prec| IsolationWindow(prec.isolation_window.clone()))
+                .filter(|window| window.state() != ffi::IsolationWindowState::Unknown)
+            else {
+                continue;
+            };
+            windows
+                .entry(DIAWindowKey::new(&window))
+                .or_insert_with(|| window.as_dia_window());
+            if let Some(count) = cycles.last_mut() {
+                *count += 1;
+            }
+        };
+        self.0.set_detail_level(detail_level);
+        self.reset();
+        result?;
+
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for count in cycles.iter().filter(|count| **count > 0) {
+            *sizes.entry(*count).or_default() += 1;
+        }
+        let cycle_length = sizes
+            .iter()
+            .max_by_key(|(size, n)| (**n, **size))
+            .map(|(size, _)| *size)
+            .unwrap_or_default();
+        let windows: Vec<ffi::DIAWindow> = windows.into_values().collect();
+        let is_overlapping = windows
+            .windows(2)
+            .any(|pair| pair[1].lower_bound < pair[0].upper_bound - DIA_WINDOW_TOLERANCE);
+        Ok(ffi::DIAWindowScheme {
+            cycle_length,
+            cycle_count: sizes.values().sum(),
+            is_overlapping,
+            is_staggered: is_overlapping && cycle_length < windows.len(),
+            windows,
+        })
+    }
 
-impl IonMobilityFrame {
-    pub fn id(&self) -> &str {
-        self.0.id()
+    /// Iterate over acquisition cycles from the current position, each an MS1 spectrum
+    /// followed by the MSn spectra acquired before the next MS1 spectrum
+    pub fn iter_cycles(&mut self) -> Box<DIACycleIterator<'_>> {
+        Box::new(DIACycleIterator(self))
+    }
+
+    /// Build one extracted ion chromatogram per target m/z from the spectra of `ms_level`
+    /// between `rt_start` and `rt_end` in a single pass. The reader is reset afterwards.
+    pub fn extract_xics(
+        &mut self,
+        targets: &[f64],
+        tolerance_ppm: f64,
+        rt_start: f64,
+        rt_end: f64,
+        ms_level: u8,
+    ) -> Result<Vec<ffi::IonTrace>, BridgeError> {
+        let tolerance = Tolerance::PPM(tolerance_ppm);
+        let mut traces: Vec<_> = targets
+            .iter()
+            .map(|mz| ffi::IonTrace {
+                target_mz: *mz,
+                time: Vec::new(),
+                intensity: Vec::new(),
+            })
+            .collect();
+
+        if self.start_from_time(rt_start).is_err() {
+            self.reset();
+        }
+        let result = self.fill_xics(&mut traces, tolerance, rt_start, rt_end, ms_level);
+        self.reset();
+        result?;
+        Ok(traces)
+    }
+
+    /// Read spectra from the current position, adding a point to each trace for every
+    /// spectrum of `ms_level` between `rt_start` and `rt_end`
+    fn fill_xics(
+        &mut self,
+        traces: &mut [ffi::IonTrace],
+        tolerance: Tolerance,
+        rt_start: f64,
+        rt_end: f64,
+        ms_level: u8,
+    ) -> Result<(), Error> {
+        while let Some(mut spec) = self.read_next()? {
+            let time = spec.start_time();
+            if time > rt_end {
+                break;
+            }
+            if time < rt_start || spec.ms_level() != ms_level {
+                continue;
+            }
+            if matches!(spec.signal_continuity(), SignalContinuityImpl::Centroid) {
+                spec.try_build_peaks().ok();
+            }
+            match spec.peaks() {
+                RefPeakDataLevel::Centroid(peaks) => {
+                    for trace in traces.iter_mut() {
+                        let peaks = peaks.all_peaks_for(trace.target_mz, tolerance);
+                        trace.time.push(time);
+                        trace
+                            .intensity
+                            .push(peaks.iter().map(|p| p.intensity).sum());
+                    }
+                }
+                RefPeakDataLevel::RawData(arrays) => {
+                    let mzs = arrays.mzs()?;
+                    let intensities = arrays.intensities()?;
+                    for trace in traces.iter_mut() {
+                        trace.time.push(time);
+                        trace.intensity.push(window_sum(
+                            &mzs,
+                            &intensities,
+                            trace.target_mz,
+                            tolerance,
+                        ));
+                    }
+                }
+                _ => {
+                    for trace in traces.iter_mut() {
+                        trace.time.push(time);
+                        trace.intensity.push(0.0);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn has_ion_mobility_dimension(&mut self) -> bool {
+        matches!(self.0.has_ion_mobility().unwrap_or_default(), HasIonMobility::Dimension)
+    }
+
+    /// Sample the file to find how ion mobility is measured, without moving the reader
+    pub fn has_ion_mobility(&mut self) -> ffi::HasIonMobility {
+        self.0.has_ion_mobility().unwrap_or_default().into()
+    }
+
+    /// Convert this reader into a frame reader, resuming from a spectrum already read by
+    /// `has_next`
+    pub fn into_frame_reader(mut self: Box<Self>) -> Result<Box<IMMZReader>, BridgeError> {
+        if let Some(spec) = self.1.take() {
+            self.0.start_from_index(spec.index())?;
+        }
+        Ok(Box::new(IMMZReader(self.0.try_into_frame_source()?, None)))
+    }
+}
+
+pub fn open(path: &str) -> Result<Box<MZReader>, BridgeError> {
+    MZReader::open(path)
+}
+
+pub fn into_frame_reader(reader: Box<MZReader>) -> Result<Box<IMMZReader>, BridgeError> {
+    reader.into_frame_reader()
+}
+
+pub fn extract_xics(
+    reader: &mut MZReader,
+    targets: &[f64],
+    tolerance_ppm: f64,
+    rt_start: f64,
+    rt_end: f64,
+    ms_level: u8,
+) -> Result<Vec<ffi::IonTrace>, BridgeError> {
+    reader.extract_xics(targets, tolerance_ppm, rt_start, rt_end, ms_level)
+}
+
+pub struct ChromatogramIterator<'a>(
+    mzdata::io::ChromatogramIterator<'a, mzdata::MZReader<std::fs::File>>,
+);
+
+impl ChromatogramIterator<'_> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Box<Chromatogram>, BridgeError> {
+        option_box_or_err!(
+            self.0.next().map(Chromatogram),
+            EndOfFile,
+            "Failed to read next chromatogram"
+        )
+    }
+}
+
+/// The m/z tolerance within which isolation windows are considered the same
+const DIA_WINDOW_TOLERANCE: f32 = 0.01;
+
+/// Isolation window bounds rounded to [`DIA_WINDOW_TOLERANCE`] so they can be compared
+/// and ordered exactly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct DIAWindowKey(i64, i64);
+
+impl DIAWindowKey {
+    fn new(window: &IsolationWindow) -> Self {
+        let round = |x: f32| (x / DIA_WINDOW_TOLERANCE).round() as i64;
+        Self(round(window.lower_bound()), round(window.upper_bound()))
+    }
+}
+
+pub struct DIACycleIterator<'a>(&'a mut MZReader);
+
+impl DIACycleIterator<'_> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Box<DIACycle>, BridgeError> {
+        let mut cycle = DIACycle {
+            ms1: None,
+            msn: Vec::new(),
+        };
+        while let Some(spec) = self.0.read_next()? {
+            if spec.ms_level() == 1 {
+                if cycle.ms1.is_some() || !cycle.msn.is_empty() {
+                    self.0 .1 = Some(spec);
+                    break;
+                }
+                cycle.ms1 = Some(Spectrum(spec));
+            } else {
+                cycle.msn.push(Spectrum(spec));
+            }
+        }
+        if cycle.ms1.is_none() && cycle.msn.is_empty() {
+            return Err(Error::new(ffi::ErrorKind::EndOfFile, "Failed to read next cycle").into());
+        }
+        cycle.msn.sort_by_key(|spec| {
+            spec.0
+                .precursor()
+                .map(|prec| DIAWindowKey::new(&IsolationWindow(prec.isolation_window.clone())))
+        });
+        Ok(Box::new(cycle))
+    }
+}
+
+/// An MS1 spectrum and the MSn spectra acquired after it, ordered by isolation window
+#[derive(Debug, Clone)]
+pub struct DIACycle {
+    ms1: Option<Spectrum>,
+    msn: Vec<Spectrum>,
+}
+
+impl DIACycle {
+    pub fn has_ms1(&self) -> bool {
+        self.ms1.is_some()
+    }
+
+    pub fn ms1(&self) -> Result<Box<Spectrum>, BridgeError> {
+        option_box_or_err!(self.ms1.clone(), "No MS1 spectrum in cycle")
+    }
+
+    pub fn msn_count(&self) -> usize {
+        self.msn.len()
+    }
+
+    pub fn msn_at(&self, index: usize) -> Result<Box<Spectrum>, BridgeError> {
+        option_box_or_err!(
+            self.msn.get(index).cloned(),
+            IndexOutOfRange,
+            format!("spectrum {index} not found in cycle")
+        )
+    }
+}
+
+/// An ion mobility frame reader, holding at most one frame read ahead by [`IMMZReader::has_next`]
+pub struct IMMZReader(
+    mzdata::io::IMMZReaderType<std::fs::File>,
+    Option<IonMobilityFrameImpl>,
+);
+
+impl IMMZReader {
+    pub fn open(path: &str) -> Result<Box<Self>, BridgeError> {
+        let reader = mzdata::MZReader::open_path(path)?;
+        Ok(Box::new(Self(reader.into_frame_source(), None)))
+    }
+
+    /// Read the next frame, returning `Ok(None)` at the end of the stream and
+    /// an error if the underlying parser failed.
+    fn read_next(&mut self) -> Result<Option<IonMobilityFrameImpl>, Error> {
+        if let Some(frame) = self.1.take() {
+            return Ok(Some(frame));
+        }
+        if let Some(frame) = self.0.next() {
+            return Ok(Some(frame));
+        }
+        let failed = match &mut self.0 {
+            mzdata::io::IMMZReaderType::MzML(reader) => {
+                reader.get_mut().state == MzMLParserState::ParserError
+            }
+            _ => false,
+        };
+        if failed {
+            Err(Error::new(
+                ffi::ErrorKind::ParseError,
+                "Failed to parse next frame",
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Box<IonMobilityFrame>, BridgeError> {
+        let spec = self.read_next()?.map(IonMobilityFrame);
+
+        option_box_or_err!(spec, EndOfFile, "Failed to read next frame")
+    }
+
+    pub fn has_next(&mut self) -> Result<bool, BridgeError> {
+        if self.1.is_none() {
+            self.1 = self.read_next()?;
+        }
+        Ok(self.1.is_some())
+    }
+
+    pub fn get_by_index(&mut self, index: usize) -> Result<Box<IonMobilityFrame>, BridgeError> {
+        if index >= self.0.len() {
+            return Err(Error::new(
+                ffi::ErrorKind::IndexOutOfRange,
+                format!("index {index} not found"),
+            )
+            .into());
+        }
+        option_box_or_err!(
+            self.0.get_frame_by_index(index).map(IonMobilityFrame),
+            ParseError,
+            format!("failed to read frame at index {index}")
+        )
+    }
+
+    pub fn get_by_id(&mut self, id: &str) -> Result<Box<IonMobilityFrame>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_frame_by_id(id).map(IonMobilityFrame),
+            format!("id {id} not found")
+        )
+    }
+
+    pub fn get_by_time(&mut self, time: f64) -> Result<Box<IonMobilityFrame>, BridgeError> {
+        option_box_or_err!(
+            self.0.get_frame_by_time(time).map(IonMobilityFrame),
+            format!("no frame found at time {time}")
+        )
+    }
+
+    pub fn start_from_index(&mut self, index: usize) -> Result<(), BridgeError> {
+        self.0.start_from_index(index)?;
+        self.1 = None;
+        Ok(())
+    }
+
+    pub fn start_from_id(&mut self, id: &str) -> Result<(), BridgeError> {
+        self.0.start_from_id(id)?;
+        self.1 = None;
+        Ok(())
+    }
+
+    pub fn start_from_time(&mut self, time: f64) -> Result<(), BridgeError> {
+        self.0.start_from_time(time)?;
+        self.1 = None;
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.0.reset();
+        self.1 = None;
+    }
+
+    /// Build a retention time by ion mobility intensity map for `mz` from the frames of
+    /// `ms_level` between `rt_start` and `rt_end`, binning the ion mobility dimension by
+    /// `ion_mobility_bin_width`. The reader is reset afterwards.
+    pub fn extract_mobility_map(
+        &mut self,
+        mz: f64,
+        tolerance_ppm: f64,
+        rt_start: f64,
+        rt_end: f64,
+        ms_level: u8,
+        ion_mobility_bin_width: f64,
+    ) -> Result<ffi::MobilityMap, BridgeError> {
+        if ion_mobility_bin_width.is_nan() || ion_mobility_bin_width <= 0.0 {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                "ion mobility bin width must be positive",
+            )
+            .into());
+        }
+        if self.start_from_time(rt_start).is_err() {
+            self.reset();
+        }
+        let result = self.read_mobilograms(mz, tolerance_ppm, rt_start, rt_end, ms_level);
+        self.reset();
+        let mobilograms = result?;
+
+        let (im_low, im_high) = mobilograms
+            .iter()
+            .flat_map(|(_, mobilogram)| mobilogram.ion_mobility.iter().copied())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), im| {
+                (lo.min(im), hi.max(im))
+            });
+        let mut map = ffi::MobilityMap {
+            target_mz: mz,
+            time: mobilograms.iter().map(|(time, _)| *time).collect(),
+            ion_mobility_start: 0.0,
+            ion_mobility_bin_width,
+            ion_mobility_bin_count: 0,
+            intensity: Vec::new(),
+        };
+        if im_low > im_high {
+            return Ok(map);
+        }
+        map.ion_mobility_start = im_low;
+        map.ion_mobility_bin_count = ((im_high - im_low) / ion_mobility_bin_width) as usize + 1;
+        map.intensity = vec![0.0; map.time.len() * map.ion_mobility_bin_count];
+        for (row, (_, mobilogram)) in mobilograms.iter().enumerate() {
+            let offset = row * map.ion_mobility_bin_count;
+            for (im, int) in mobilogram
+                .ion_mobility
+                .iter()
+                .zip(mobilogram.intensity.iter())
+            {
+                let col = (((im - im_low) / ion_mobility_bin_width) as usize)
+                    .min(map.ion_mobility_bin_count - 1);
+                map.intensity[offset + col] += int;
+            }
+        }
+        Ok(map)
+    }
+
+    /// Read frames from the current position, extracting the mobilogram of `mz` from each
+    /// frame of `ms_level` between `rt_start` and `rt_end`
+    fn read_mobilograms(
+        &mut self,
+        mz: f64,
+        tolerance_ppm: f64,
+        rt_start: f64,
+        rt_end: f64,
+        ms_level: u8,
+    ) -> Result<Vec<(f64, ffi::Mobilogram)>, BridgeError> {
+        let mut mobilograms = Vec::new();
+        while let Some(frame) = self.read_next()? {
+            let time = frame.start_time();
+            if time > rt_end {
+                break;
+            }
+            if time < rt_start || frame.ms_level() != ms_level {
+                continue;
+            }
+            let mobilogram = IonMobilityFrame(frame).extract_mobilogram(mz, tolerance_ppm)?;
+            mobilograms.push((time, mobilogram));
+        }
+        Ok(mobilograms)
+    }
+
+    pub fn metadata(&self) -> Box<RunMetadata> {
+        Box::new(RunMetadata(FileMetadataConfig::from(&self.0)))
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+pub fn open_im(path: &str) -> Result<Box<IMMZReader>, BridgeError> {
+    IMMZReader::open(path)
+}
+
+pub fn extract_mobility_map(
+    reader: &mut IMMZReader,
+    mz: f64,
+    tolerance_ppm: f64,
+    rt_start: f64,
+    rt_end: f64,
+    ms_level: u8,
+    ion_mobility_bin_width: f64,
+) -> Result<ffi::MobilityMap, BridgeError> {
+    reader.extract_mobility_map(
+        mz,
+        tolerance_ppm,
+        rt_start,
+        rt_end,
+        ms_level,
+        ion_mobility_bin_width,
+    )
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum MZWriterType {
+    MzML(mzdata::MzMLWriter<std::fs::File>),
+    MGF(mzdata::MGFWriter<std::fs::File>),
+}
+
+pub struct MZWriter(Option<MZWriterType>);
+
+impl MZWriter {
+    pub fn create(
+        path: &str,
+        format: ffi::MassSpectrometryFormat,
+    ) -> Result<Box<Self>, BridgeError> {
+        let handle = std::fs::File::create(path)?;
+        let writer = match format.into() {
+            MassSpectrometryFormatImpl::MzML => {
+                MZWriterType::MzML(mzdata::MzMLWriter::new_with_index(handle, true))
+            }
+            MassSpectrometryFormatImpl::MGF => MZWriterType::MGF(mzdata::MGFWriter::new(handle)),
+            fmt => {
+                return Err(Error::new(
+                    ffi::ErrorKind::UnsupportedFormat,
+                    format!("Writing {fmt:?} is not supported"),
+                )
+                .into())
+            }
+        };
+        Ok(Box::new(Self(Some(writer))))
+    }
+
+    fn writer_mut(&mut self) -> Result<&mut MZWriterType, Error> {
+        self.0
+            .as_mut()
+            .ok_or_else(|| Error::new(ffi::ErrorKind::IOError, "Writer already closed"))
+    }
+
+    /// Set the count written on the mzML `<spectrumList>`. The list header is written with
+    /// the first spectrum, so this fails once a spectrum has been written.
+    pub fn set_spectrum_count(&mut self, count: u64) -> Result<(), BridgeError> {
+        if let MZWriterType::MzML(writer) = self.writer_mut()? {
+            if writer.state >= MzMLWriterState::SpectrumList {
+                return Err(Error::new(
+                    ffi::ErrorKind::IOError,
+                    "The spectrum count must be set before the first spectrum is written",
+                )
+                .into());
+            }
+            writer.spectrum_count = count;
+        }
+        Ok(())
+    }
+
+    pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<(), BridgeError> {
+        match self.writer_mut()? {
+            MZWriterType::MzML(writer) => SpectrumWriter::write(writer, &spectrum.0)?,
+            MZWriterType::MGF(writer) => SpectrumWriter::write(writer, &spectrum.0)?,
+        };
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), BridgeError> {
+        match self.writer_mut()? {
+            MZWriterType::MzML(writer) => SpectrumWriter::flush(writer)?,
+            MZWriterType::MGF(writer) => SpectrumWriter::flush(writer)?,
+        };
+        Ok(())
+    }
+
+    pub fn close(&mut self) -> Result<(), BridgeError> {
+        match self.0.take() {
+            Some(MZWriterType::MzML(mut writer)) => SpectrumWriter::close(&mut writer)?,
+            Some(MZWriterType::MGF(mut writer)) => SpectrumWriter::close(&mut writer)?,
+            None => {}
+        };
+        Ok(())
+    }
+}
+
+pub fn create(
+    path: &str,
+    format: ffi::MassSpectrometryFormat,
+) -> Result<Box<MZWriter>, BridgeError> {
+    MZWriter::create(path, format)
+}
+
+
+#[derive(Debug, Clone)]
+pub struct SelectedIon(SelectedIonImpl);
+
+impl SelectedIon {
+    pub fn mz(&self) -> f64 {
+        self.0.mz
+    }
+
+    pub fn charge(&self, value: &mut i32) -> bool {
+        option_bool!(self.0.charge, value)
+    }
+
+    pub fn neutral_mass(&self) -> f64 {
+        self.0.neutral_mass()
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.0.intensity
+    }
+
+    pub fn ion_mobility(&self, value: &mut f64) -> bool {
+        option_bool!(self.0.ion_mobility(), value)
+    }
+
+    param_methods!();
+}
+
+impl IonProperties for SelectedIon {
+    #[inline]
+    fn mz(&self) -> f64 {
+        <SelectedIonImpl as IonProperties>::mz(&self.0)
+    }
+
+    #[inline]
+    fn neutral_mass(&self) -> f64 {
+        <SelectedIonImpl as IonProperties>::neutral_mass(&self.0)
+    }
+
+    #[inline]
+    fn charge(&self) -> Option<i32> {
+        <SelectedIonImpl as IonProperties>::charge(&self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Precursor<'a>(&'a PrecursorImpl);
+
+impl Precursor<'_> {
+    pub fn selected_mz(&self, value: &mut f64) -> bool {
+        option_bool!(self.0.ions.first().map(|i| i.mz), value)
+    }
+
+    pub fn selected_charge(&self, value: &mut i32) -> bool {
+        option_bool!(self.0.ions.first().and_then(|i| i.charge), value)
+    }
+
+    pub fn selected_ion_mobility(&self, value: &mut f64) -> bool {
+        option_bool!(self.0.ions.first().and_then(|i| i.ion_mobility()), value)
+    }
+
+    pub fn ion_count(&self) -> usize {
+        self.0.ions.len()
+    }
+
+    pub fn ion_at(&self, index: usize) -> Result<Box<SelectedIon>, BridgeError> {
+        option_box_or_err!(
+            self.0.ions.get(index).cloned().map(SelectedIon),
+            IndexOutOfRange,
+            format!("selected ion {index} not found")
+        )
+    }
+
+    pub fn isolation_window(&self) -> Result<Box<IsolationWindow>, BridgeError> {
+        match self.0.isolation_window.flags {
+            IsolationWindowStateImpl::Unknown => {
+                Err(Error::new(ffi::ErrorKind::NotFound, "No isolation window found").into())
+            }
+            _ => Ok(Box::new(IsolationWindow(self.0.isolation_window.clone()))),
+        }
+    }
+
+    /// Get the isolation window even when its state is unknown, which callers can
+    /// check with [`IsolationWindow::state`]
+    pub fn get_isolation_window(&self) -> Box<IsolationWindow> {
+        Box::new(IsolationWindow(self.0.isolation_window.clone()))
+    }
+
+    pub fn precursor_id(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.precursor_id.as_deref() {
+            out.as_mut().clear();
+            out.as_mut().push_str(val);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn product_id(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.product_id.as_deref() {
+            out.as_mut().clear();
+            out.as_mut().push_str(val);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn activation_energy(&self, value: &mut f32) -> bool {
+        if self.0.activation.energy == 0.0 {
+            return false;
+        }
+        *value = self.0.activation.energy;
+        true
+    }
+
+    pub fn activation(&self) -> Box<Activation<'_>> {
+        Box::new(Activation(&self.0.activation))
+    }
+
+    pub fn activation_method_is_combined(&self) -> bool {
+        self.0.activation.is_combined()
+    }
+
+    pub fn activation_methods(&self) -> Vec<ffi::CURIE> {
+        self.0
+            .activation
+            .methods()
+            .iter()
+            .map(|method| {
+                let acc = method.accession();
+                let cv = method.controlled_vocabulary();
+                ffi::CURIE::from(CURIEImpl::new(cv, acc))
+            })
+            .collect()
+    }
+
+    pub fn activation_method(&self, value: &mut ffi::CURIE) -> bool {
+        if let Some(method) = self.0.activation.method() {
+            let acc = method.accession();
+            let cv = method.controlled_vocabulary();
+            *value = ffi::CURIE::from(CURIEImpl::new(cv, acc));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Activation<'a>(&'a ActivationImpl);
+
+impl Activation<'_> {
+    /// The dissociation energy terms stored among the activation's params
+    fn energy_params(&self) -> impl Iterator<Item = DissociationEnergyTerm> + '_ {
+        self.0.params().iter().filter_map(|param| {
+            let value = param.to_f32().ok()?;
+            DissociationEnergyTerm::from_curie(&param.curie()?, value)
+        })
+    }
+
+    pub fn has_energy(&self) -> bool {
+        self.0.energy != 0.0 || self.energy_params().any(|term| !term.is_supplemental())
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.0.energy
+    }
+
+    /// The recorded non-supplemental dissociation energies, the main energy followed by
+    /// any other energy params on the activation
+    pub fn energies(&self) -> Vec<f32> {
+        let mut energies = Vec::new();
+        if self.0.energy != 0.0 {
+            energies.push(self.0.energy);
+        }
+        for term in self.energy_params() {
+            if !term.is_supplemental() && !energies.contains(&term.energy()) {
+                energies.push(term.energy());
+            }
+        }
+        energies
+    }
+
+    pub fn supplemental_energy(&self, value: &mut f32) -> bool {
+        option_bool!(
+            self.energy_params()
+                .find(|term| term.is_supplemental())
+                .map(|term| term.energy()),
+            value
+        )
+    }
+
+    pub fn methods(&self) -> Vec<ffi::ActivationMethod> {
+        self.0
+            .methods()
+            .iter()
+            .map(|method| ffi::ActivationMethod {
+                curie: CURIEImpl::new(method.controlled_vocabulary(), method.accession()).into(),
+                name: method.name().to_string(),
+                is_supplemental: matches!(
+                    method,
+                    DissociationMethodTerm::SupplementalBeamTypeCollisionInducedDissociation
+                        | DissociationMethodTerm::SupplementalCollisionInducedDissociation
+                ),
+            })
+            .collect()
+    }
+
+    pub fn is_combined(&self) -> bool {
+        self.0.is_combined()
+    }
+
+    /// Whether any supplemental activation method or energy was used, as in EThcD or ETciD
+    pub fn is_supplemental(&self) -> bool {
+        self.methods().iter().any(|method| method.is_supplemental)
+            || self.energy_params().any(|term| term.is_supplemental())
+    }
+
+    param_methods!();
+}
+
+#[derive(Debug, Clone)]
+pub struct Acquisition<'a>(&'a AcquisitionImpl);
+
+impl<'a> Acquisition<'a> {
+    pub fn first_scan(&self) -> Result<Box<ScanEvent<'_>>, BridgeError> {
+        Ok(self
+            .0
+            .first_scan()
+            .map(|s| Box::new(ScanEvent(s)))
+            .ok_or_else(|| Error::new(ffi::ErrorKind::NotFound, "Scan not found"))?)
+    }
+
+    pub fn scan(&self, index: usize) -> Result<Box<ScanEvent<'_>>, BridgeError> {
+        Ok(self
+            .0
+            .scans
+            .get(index)
+            .map(|s| Box::new(ScanEvent(s)))
+            .ok_or_else(|| Error::new(ffi::ErrorKind::IndexOutOfRange, "Scan not found"))?)
+    }
+
+    pub fn instrument_configuration_ids(&self) -> Vec<u32> {
+        self.0.instrument_configuration_ids()
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.0.start_time()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    param_methods!();
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanEvent<'a>(&'a ScanEventImpl);
+
+impl<'a> ScanEvent<'a> {
+    param_methods!();
+
+    pub fn start_time(&self) -> f64 {
+        self.0.start_time
+    }
+
+    pub fn injection_time(&self) -> f32 {
+        self.0.injection_time
+    }
+
+    pub fn instrument_configuration_id(&self) -> u32 {
+        self.0.instrument_configuration_id
+    }
+
+    pub fn has_ion_mobility(&self) -> bool {
+        self.0.has_ion_mobility()
+    }
+
+    pub fn ion_mobility(&self, value: &mut f64) -> bool {
+        if let Some(val) = self.0.ion_mobility() {
+            *value = val;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn filter_string(&self, mut out: Pin<&mut CxxString>) -> bool {
+        self.0.filter_string().map(|s| {
+            out.as_mut().clear();
+            out.as_mut().push_str(&s);
+            true
+        }).unwrap_or_default()
+    }
+
+    pub fn scan_configuration(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.scan_configuration() {
+            out.as_mut().push_str(&val.to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn scan_windows(&self) -> Vec<ffi::ScanWindow> {
+        self.0
+            .scan_windows
+            .iter()
+            .map(|w| ffi::ScanWindow {
+                lower_bound: w.lower_bound,
+                upper_bound: w.upper_bound,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Spectrum(SpectrumImpl);
+
+impl Spectrum {
+    pub fn id(&self) -> &str {
+        self.0.id()
+    }
+
+    pub fn index(&self) -> usize {
+        self.0.index()
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.0.start_time()
+    }
+
+    pub fn ms_level(&self) -> u8 {
+        self.0.ms_level()
+    }
+
+    pub fn is_profile(&self) -> bool {
+        matches!(
+            self.0.signal_continuity(),
+            mzdata::spectrum::SignalContinuity::Profile
+        )
+    }
+
+    pub fn mzs_into(&self, mut container: Pin<&mut CxxVector<f64>>) {
+        for pt in self.0.peaks().iter() {
+            container.as_mut().push(pt.mz)
+        }
+    }
+
+    pub fn intensities_into(&self, mut container: Pin<&mut CxxVector<f32>>) {
+        for pt in self.0.peaks().iter() {
+            container.as_mut().push(pt.intensity)
+        }
+    }
+
+    pub fn signal_into(
+        &self,
+        mut mzs_container: Pin<&mut CxxVector<f64>>,
+        mut intensities_container: Pin<&mut CxxVector<f32>>,
+    ) {
+        for pt in self.0.peaks().iter() {
+            mzs_container.as_mut().push(pt.mz);
+            intensities_container.as_mut().push(pt.intensity);
+        }
+    }
+
+    /// Get the spectrum's arrays, building them from its peak list if it has
+    /// no raw arrays of its own
+    fn arrays_mut(&mut self) -> Result<&mut BinaryArrayMap, Error> {
+        if self.0.arrays.is_none() {
+            if let Some(peaks) = self.0.peaks.as_ref() {
+                self.0.arrays = Some(BinaryArrayMap::from(peaks));
+            } else if let Some(peaks) = self.0.deconvoluted_peaks.as_ref() {
+                self.0.arrays = Some(BinaryArrayMap::from(peaks));
+            }
+        }
+        self.0
+            .arrays
+            .as_mut()
+            .ok_or_else(|| Error::new(ffi::ErrorKind::NotFound, "No signal arrays found"))
+    }
+
+    /// Borrow the m/z array, decoding it in place on first access
+    pub fn mzs_view(&mut self) -> Result<&[f64], BridgeError> {
+        Ok(view_array(
+            self.arrays_mut()?,
+            &ArrayTypeImpl::MZArray,
+            BinaryDataArrayTypeImpl::Float64,
+        )?)
+    }
+
+    /// Borrow the intensity array, decoding it in place on first access
+    pub fn intensities_view(&mut self) -> Result<&[f32], BridgeError> {
+        Ok(view_array(
+            self.arrays_mut()?,
+            &ArrayTypeImpl::IntensityArray,
+            BinaryDataArrayTypeImpl::Float32,
+        )?)
+    }
+
+    pub fn polarity(&self) -> ffi::ScanPolarity {
+        self.0.polarity().into()
+    }
+
+    pub fn signal_continuity(&self) -> ffi::SignalContinuity {
+        self.0.signal_continuity().into()
+    }
+
+    pub fn set_id(&mut self, id: &str) {
+        self.0.description_mut().id = id.to_string();
+    }
+
+    pub fn set_index(&mut self, index: usize) {
+        self.0.description_mut().index = index;
+    }
+
+    pub fn set_ms_level(&mut self, ms_level: u8) {
+        self.0.description_mut().ms_level = ms_level;
+    }
+
+    pub fn set_start_time(&mut self, start_time: f64) {
+        let scans = &mut self.0.description_mut().acquisition.scans;
+        if scans.is_empty() {
+            scans.push(ScanEventImpl::default());
+        }
+        scans[0].start_time = start_time;
+    }
+
+    pub fn set_polarity(&mut self, polarity: ffi::ScanPolarity) {
+        self.0.description_mut().polarity = polarity.into();
+    }
+
+    pub fn set_signal_continuity(&mut self, signal_continuity: ffi::SignalContinuity) {
+        self.0.description_mut().signal_continuity = signal_continuity.into();
+    }
+
+    /// Replace an array, dropping any peak list and summary params derived from the
+    /// previous arrays
+    fn set_array(&mut self, array: DataArrayImpl) {
+        self.0.peaks = None;
+        self.0.deconvoluted_peaks = None;
+        self.0
+            .description_mut()
+            .params
+            .retain(|param| !param.curie().is_some_and(|c| SUMMARY_PARAMS.contains(&c)));
+        self.0
+            .arrays
+            .get_or_insert_with(BinaryArrayMap::new)
+            .add(array);
+    }
+
+    /// Fail if the array `other` is already present with a length other than `len`
+    fn check_array_len(&self, other: &ArrayTypeImpl, len: usize) -> Result<(), Error> {
+        let Some(array) = self.0.arrays.as_ref().and_then(|arrays| arrays.get(other)) else {
+            return Ok(());
+        };
+        let other_len = array.data_len()?;
+        if other_len != len {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                format!("Expected {other_len} values to match the {other}, got {len}"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn mz_array(mzs: &[f64]) -> Result<DataArrayImpl, Error> {
+        let mut array = DataArrayImpl::from_name_and_type(
+            &ArrayTypeImpl::MZArray,
+            BinaryDataArrayTypeImpl::Float64,
+        );
+        array.extend(mzs)?;
+        array.unit = Unit::MZ;
+        Ok(array)
+    }
+
+    fn intensity_array(intensities: &[f32]) -> Result<DataArrayImpl, Error> {
+        let mut array = DataArrayImpl::from_name_and_type(
+            &ArrayTypeImpl::IntensityArray,
+            BinaryDataArrayTypeImpl::Float32,
+        );
+        array.extend(intensities)?;
+        array.unit = Unit::DetectorCounts;
+        Ok(array)
+    }
+
+    pub fn set_mzs(&mut self, mzs: &[f64]) -> Result<(), BridgeError> {
+        self.check_array_len(&ArrayTypeImpl::IntensityArray, mzs.len())?;
+        self.set_array(Self::mz_array(mzs)?);
+        Ok(())
+    }
+
+    pub fn set_intensities(&mut self, intensities: &[f32]) -> Result<(), BridgeError> {
+        self.check_array_len(&ArrayTypeImpl::MZArray, intensities.len())?;
+        self.set_array(Self::intensity_array(intensities)?);
+        Ok(())
+    }
+
+    /// Replace both the m/z and intensity arrays, which may change the signal's length
+    pub fn set_signal(&mut self, mzs: &[f64], intensities: &[f32]) -> Result<(), BridgeError> {
+        if mzs.len() != intensities.len() {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                format!(
+                    "Expected as many intensities as m/z values, got {} and {}",
+                    intensities.len(),
+                    mzs.len()
+                ),
+            )
+            .into());
+        }
+        self.set_array(Self::mz_array(mzs)?);
+        self.set_array(Self::intensity_array(intensities)?);
+        Ok(())
+    }
+
+    fn precursor_mut(&mut self) -> &mut PrecursorImpl {
+        self.0
+            .description_mut()
+            .precursor
+            .get_or_insert_with(PrecursorImpl::default)
+    }
+
+    /// Set the selected ion of the precursor, creating the precursor if needed.
+    /// A `charge` of 0 marks the charge state as unknown.
+    pub fn set_precursor(&mut self, mz: f64, charge: i32) {
+        let ions = &mut self.precursor_mut().ions;
+        if ions.is_empty() {
+            ions.push(SelectedIonImpl::default());
+        }
+        ions[0].mz = mz;
+        ions[0].charge = (charge != 0).then_some(charge);
+    }
+
+    pub fn set_isolation_window(&mut self, target: f32, lower_offset: f32, upper_offset: f32) {
+        self.precursor_mut().isolation_window = IsolationWindowImpl::new(
+            target,
+            target - lower_offset,
+            target + upper_offset,
+            IsolationWindowStateImpl::Complete,
+        );
+    }
+
+    /// Set the dissociation method by its PSI-MS term and the activation energy
+    pub fn set_activation(&mut self, method: &ffi::CURIE, energy: f32) -> Result<(), BridgeError> {
+        let term = match method.controlled_vocabulary {
+            ffi::ControlledVocabulary::MS => {
+                DissociationMethodTerm::from_accession(method.accession)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| {
+            Error::new(
+                ffi::ErrorKind::NotFound,
+                format!("{} is not a dissociation method", CURIEImpl::from(*method)),
+            )
+        })?;
+        let activation = &mut self.precursor_mut().activation;
+        let methods = activation.methods_mut();
+        methods.clear();
+        methods.push(term);
+        activation.energy = energy;
+        Ok(())
+    }
+
+    pub fn add_param(&mut self, param: &Param) {
+        self.0.params_mut().push(param.0.clone());
+    }
+
+    pub fn add_user_param(&mut self, name: &str, value: &str) {
+        self.0.params_mut().push(ParamImpl::new_key_value(
+            name,
+            Value::new(value.to_string()),
+        ));
+    }
+
+    pub fn add_cv_param(&mut self, curie: &ffi::CURIE, name: &str, value: &str) {
+        let mut param = ParamImpl::new_key_value(name, Value::new(value.to_string()));
+        param.controlled_vocabulary = Some(curie.controlled_vocabulary.into());
+        param.accession = Some(curie.accession);
+        self.0.params_mut().push(param);
+    }
+
+    fn raw_arrays(&self) -> Option<&BinaryArrayMap> {
+        self.0.arrays.as_ref()
+    }
+
+    array_methods!();
+
+    fn summary(&self) -> SpectrumSummary {
+        self.0.peaks().fetch_summaries()
+    }
+
+    /// Build the peak list `try_build_peaks` would cache for a centroid spectrum read with
+    /// only its arrays
+    fn built_peaks(&self) -> Option<PeakSet> {
+        if !matches!(self.0.signal_continuity(), SignalContinuityImpl::Centroid) {
+            return None;
+        }
+        match self.0.peaks() {
+            RefPeakDataLevel::RawData(arrays) => {
+                CentroidPeak::try_from_arrays(arrays).ok().map(PeakSet::new)
+            }
+            _ => None,
+        }
+    }
+
+    /// Find the peak, or raw data point for profile spectra, closest to `mz` within `tolerance`
+    pub fn find_peak(
+        &self,
+        mz: f64,
+        tolerance: f64,
+        unit: ffi::ToleranceUnit,
+        value: &mut ffi::PeakMatch,
+    ) -> bool {
+        let built = self.built_peaks();
+        let peaks = match built.as_ref() {
+            Some(peaks) => RefPeakDataLevel::Centroid(peaks),
+            None => self.0.peaks(),
+        };
+        let found = peaks
+            .search(mz, to_tolerance(tolerance, unit))
+            .and_then(|index| peaks.get(index).map(|point| (index, point)));
+        if let Some((index, point)) = found {
+            *value = ffi::PeakMatch {
+                index,
+                mz: point.mz,
+                intensity: point.intensity,
+            };
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Find every peak, or raw data point for profile spectra, within `tolerance` of `mz`
+    pub fn find_all_peaks(
+        &self,
+        mz: f64,
+        tolerance: f64,
+        unit: ffi::ToleranceUnit,
+    ) -> Vec<ffi::PeakMatch> {
+        let tolerance = to_tolerance(tolerance, unit);
+        let built = self.built_peaks();
+        let peaks = match built.as_ref() {
+            Some(peaks) => RefPeakDataLevel::Centroid(peaks),
+            None => self.0.peaks(),
+        };
+        match peaks {
+            RefPeakDataLevel::Centroid(peaks) => peaks
+                .all_peaks_for(mz, tolerance)
+                .iter()
+                .map(|peak| ffi::PeakMatch {
+                    index: peak.get_index() as usize,
+                    mz: peak.mz,
+                    intensity: peak.intensity,
+                })
+                .collect(),
+            RefPeakDataLevel::RawData(arrays) => {
+                let (Ok(mzs), Ok(intensities)) = (arrays.mzs(), arrays.intensities()) else {
+                    return Vec::new();
+                };
+                let (low, high) = tolerance.bounds(mz);
+                let start = mzs.partition_point(|mz| *mz < low);
+                let end = mzs.partition_point(|mz| *mz <= high).min(intensities.len());
+                (start..end)
+                    .map(|index| ffi::PeakMatch {
+                        index,
+                        mz: mzs[index],
+                        intensity: intensities[index],
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Centroid the spectrum's raw arrays into its peak list, keeping the arrays
+    pub fn pick_peaks(&mut self, signal_to_noise_threshold: f32) -> Result<(), BridgeError> {
+        self.0.pick_peaks(signal_to_noise_threshold)?;
+        Ok(())
+    }
+
+    pub fn pick_peaks_with_fit(
+        &mut self,
+        signal_to_noise_threshold: f32,
+        fit_type: ffi::PeakFitType,
+    ) -> Result<(), BridgeError> {
+        let peak_picker = PeakPicker {
+            fit_type: fit_type.into(),
+            signal_to_noise_threshold,
+            ..Default::default()
+        };
+        self.0.pick_peaks_with(&peak_picker)?;
+        Ok(())
+    }
+
+    pub fn centroid_mzs_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> bool {
+        self.0
+            .peaks
+            .as_ref()
+            .map(|peaks| {
+                for peak in peaks.iter() {
+                    container.as_mut().push(peak.mz);
+                }
+                true
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn centroid_intensities_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> bool {
+        self.0
+            .peaks
+            .as_ref()
+            .map(|peaks| {
+                for peak in peaks.iter() {
+                    container.as_mut().push(peak.intensity);
+                }
+                true
+            })
+            .unwrap_or_default()
+    }
+
+    summary_methods!();
+
+    pub fn precursor(&self) -> Result<Box<Precursor<'_>>, BridgeError> {
+        option_box_or_err!(self.0.precursor().map(Precursor), "No precursor found")
+    }
+
+    pub fn precursor_count(&self) -> usize {
+        self.0.precursor_iter().count()
+    }
+
+    /// Find the position of this spectrum's isolation window in `scheme.windows`
+    pub fn dia_window_index(&self, scheme: &ffi::DIAWindowScheme, value: &mut usize) -> bool {
+        let Some(prec) = self.0.precursor() else {
+            return false;
+        };
+        let window = IsolationWindow(prec.isolation_window.clone());
+        option_bool!(
+            scheme.windows.iter().position(|w| {
+                (w.lower_bound - window.lower_bound()).abs() <= DIA_WINDOW_TOLERANCE
+                    && (w.upper_bound - window.upper_bound()).abs() <= DIA_WINDOW_TOLERANCE
+            }),
+            value
+        )
+    }
+
+    pub fn precursor_at(&self, index: usize) -> Result<Box<Precursor<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.precursor_iter().nth(index).map(Precursor),
+            IndexOutOfRange,
+            format!("precursor {index} not found")
+        )
+    }
+
+    pub fn acquisition(&self) -> Box<Acquisition<'_>> {
+        Box::new(Acquisition(self.0.acquisition()))
+    }
+
+    param_methods!();
+}
+
+/// Create an empty spectrum to populate and pass to a writer
+pub fn new_spectrum() -> Box<Spectrum> {
+    Box::new(Spectrum(SpectrumImpl::default()))
+}
+
+#[derive(Debug, Clone)]
+pub struct IonMobilityFrame(IonMobilityFrameImpl);
+
+impl IonMobilityFrame {
+    pub fn id(&self) -> &str {
+        self.0.id()
+    }
+
+    pub fn index(&self) -> usize {
+        self.0.index()
+    }
+
+    pub fn start_time(&self) -> f64 {
+        self.0.start_time()
+    }
+
+    pub fn ms_level(&self) -> u8 {
+        self.0.ms_level()
+    }
+
+    pub fn is_profile(&self) -> bool {
+        matches!(
+            self.0.signal_continuity(),
+            mzdata::spectrum::SignalContinuity::Profile
+        )
+    }
+
+    pub fn ion_mobility_dimension(&self, mut out: Pin<&mut CxxVector<f64>>) -> bool {
+        self.0
+            .arrays
+            .as_ref()
+            .map(|arrays| {
+                let vals = arrays.ion_mobility_dimension.as_slice();
+                for val in vals {
+                    out.as_mut().push(*val);
+                }
+                true
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn signal_at_ion_mobility_index_into(
+        &self,
+        ion_mobility_index: usize,
+        mut mzs_container: Pin<&mut CxxVector<f64>>,
+        mut intensities_container: Pin<&mut CxxVector<f32>>,
+        ion_mobility: &mut f64,
+    ) {
+        if let Some(maps) = self.0.arrays.as_ref() {
+            if let Some(val) = maps.ion_mobility_dimension.get(ion_mobility_index) {
+                *ion_mobility = *val;
+            }
+            if let Some(arrays) = maps.arrays.get(ion_mobility_index) {
+                if let Ok(mzs) = arrays.mzs() {
+                    for mz in mzs.iter().copied() {
+                        mzs_container.as_mut().push(mz);
+                    }
+                }
+                if let Ok(ints) = arrays.intensities() {
+                    for int in ints.iter().copied() {
+                        intensities_container.as_mut().push(int);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Copy every data point of the frame into flat, equal length ion mobility, m/z and
+    /// intensity vectors in a single call. `offsets` receives the position at which each
+    /// ion mobility slice starts followed by the end of the last slice, in CSR layout.
+    pub fn signal_into(
+        &self,
+        mut ion_mobility_container: Pin<&mut CxxVector<f64>>,
+        mut mzs_container: Pin<&mut CxxVector<f64>>,
+        mut intensities_container: Pin<&mut CxxVector<f32>>,
+        mut offsets: Pin<&mut CxxVector<usize>>,
+    ) -> Result<(), BridgeError> {
+        let Some(maps) = self.0.arrays.as_ref() else {
+            return Ok(());
+        };
+        let mut offset = mzs_container.len();
+        offsets.as_mut().push(offset);
+        for (im, arrays) in maps.iter() {
+            let mzs = arrays.mzs()?;
+            let intensities = arrays.intensities()?;
+            for (mz, int) in mzs.iter().copied().zip(intensities.iter().copied()) {
+                ion_mobility_container.as_mut().push(im);
+                mzs_container.as_mut().push(mz);
+                intensities_container.as_mut().push(int);
+            }
+            offset += mzs.len().min(intensities.len());
+            offsets.as_mut().push(offset);
+        }
+        Ok(())
+    }
+
+    /// Sum the frame's signal into a dense, row-major matrix with one row per ion mobility
+    /// bin and one column per m/z bin, spanning the range of the data
+    pub fn binned_signal(
+        &self,
+        mz_bin_width: f64,
+        ion_mobility_bin_width: f64,
+    ) -> Result<ffi::BinnedFrame, BridgeError> {
+        if !(mz_bin_width > 0.0 && ion_mobility_bin_width > 0.0) {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                "bin widths must be positive",
+            )
+            .into());
+        }
+        let mut binned = ffi::BinnedFrame {
+            mz_start: 0.0,
+            mz_bin_width,
+            mz_bin_count: 0,
+            ion_mobility_start: 0.0,
+            ion_mobility_bin_width,
+            ion_mobility_bin_count: 0,
+            intensity: Vec::new(),
+        };
+        let Some(maps) = self.0.arrays.as_ref() else {
+            return Ok(binned);
+        };
+        let summary = self.summary();
+        let mut im_low = 0.0;
+        let mut im_high = 0.0;
+        if summary.is_empty() || !self.ion_mobility_range(&mut im_low, &mut im_high) {
+            return Ok(binned);
+        }
+
+        let (mz_low, mz_high) = summary.mz_range;
+        binned.mz_start = mz_low;
+        binned.mz_bin_count = bin_count(mz_low, mz_high, mz_bin_width);
+        binned.ion_mobility_start = im_low;
+        binned.ion_mobility_bin_count = bin_count(im_low, im_high, ion_mobility_bin_width);
+        let cells = grid_cells(&[binned.mz_bin_count, binned.ion_mobility_bin_count])?;
+        binned.intensity = vec![0.0; cells];
+
+        for (im, arrays) in maps.iter() {
+            let row = (((im - im_low) / ion_mobility_bin_width) as usize)
+                .min(binned.ion_mobility_bin_count - 1);
+            let mzs = arrays.mzs()?;
+            let intensities = arrays.intensities()?;
+            for (mz, int) in mzs.iter().copied().zip(intensities.iter().copied()) {
+                let col = (((mz - mz_low) / mz_bin_width) as usize).min(binned.mz_bin_count - 1);
+                binned.intensity[row * binned.mz_bin_count + col] += int;
+            }
+        }
+        Ok(binned)
+    }
+
+    /// Sum the ion mobility slices between `ion_mobility_low` and `ion_mobility_high` into
+    /// a single spectrum. Profile frames are averaged onto an m/z grid with spacing
+    /// `mz_tolerance` and scaled back up to summed intensities, while centroid frames merge
+    /// points closer than `mz_tolerance`.
+    pub fn to_spectrum(
+        &self,
+        ion_mobility_low: f64,
+        ion_mobility_high: f64,
+        mz_tolerance: f64,
+    ) -> Result<Box<Spectrum>, BridgeError> {
+        if mz_tolerance.is_nan() || mz_tolerance <= 0.0 {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                "m/z tolerance must be positive",
+            )
+            .into());
+        }
+        let mut pairs = Vec::new();
+        if let Some(maps) = self.0.arrays.as_ref() {
+            for (im, arrays) in maps.iter() {
+                if im < ion_mobility_low || im > ion_mobility_high {
+                    continue;
+                }
+                let mzs = arrays.mzs()?;
+                if mzs.is_empty() {
+                    continue;
+                }
+                pairs.push(ArrayPair::new(mzs, arrays.intensities()?));
+            }
+        }
+
+        let (mzs, intensities) = if pairs.is_empty() {
+            (Vec::new(), Vec::new())
+        } else if self.is_profile() {
+            let (mz_low, mz_high) = pairs
+                .iter()
+                .fold((f64::INFINITY, 0.0f64), |(low, high), pair| {
+                    (low.min(pair.min_mz), high.max(pair.max_mz))
+                });
+            grid_cells(&[bin_count(mz_low, mz_high, mz_tolerance)])?;
+            let scale = pairs.len() as f32;
+            let averaged = average_signal(&pairs, mz_tolerance);
+            (
+                averaged.mz_array.into_owned(),
+                averaged
+                    .intensity_array
+                    .iter()
+                    .map(|int| int * scale)
+                    .collect(),
+            )
+        } else {
+            merge_centroids(&pairs, mz_tolerance)
+        };
+
+        let mut description = SpectrumDescription::from(self.0.description().clone());
+        description
+            .params
+            .retain(|param| !param.curie().is_some_and(|c| SUMMARY_PARAMS.contains(&c)));
+        let mut spectrum = Spectrum(SpectrumImpl::default());
+        spectrum.0.description = description;
+        spectrum.set_signal(&mzs, &intensities)?;
+        Ok(Box::new(spectrum))
+    }
+
+    /// Sum the signal within `tolerance_ppm` of `mz` in each ion mobility slice
+    pub fn extract_mobilogram(
+        &self,
+        mz: f64,
+        tolerance_ppm: f64,
+    ) -> Result<ffi::Mobilogram, BridgeError> {
+        let tolerance = Tolerance::PPM(tolerance_ppm);
+        let mut mobilogram = ffi::Mobilogram {
+            target_mz: mz,
+            ion_mobility: Vec::new(),
+            intensity: Vec::new(),
+        };
+        if let Some(maps) = self.0.arrays.as_ref() {
+            for (im, arrays) in maps.iter() {
+                let mzs = arrays.mzs()?;
+                let intensities = arrays.intensities()?;
+                mobilogram.ion_mobility.push(im);
+                mobilogram
+                    .intensity
+                    .push(window_sum(&mzs, &intensities, mz, tolerance));
+            }
+        }
+        Ok(mobilogram)
+    }
+
+    /// Trace features across the ion mobility dimension of the frame, picking peaks from
+    /// profile slices first. The features are also kept on the frame.
+    pub fn extract_features(
+        &mut self,
+        mz_tolerance_ppm: f64,
+        min_length: usize,
+        max_gap: f64,
+    ) -> Result<Vec<ffi::IonMobilityFeature>, BridgeError> {
+        if matches!(self.0.signal_continuity(), SignalContinuityImpl::Unknown) {
+            return Err(Error::new(
+                ffi::ErrorKind::ProcessingError,
+                "Cannot extract features from a frame with unknown signal continuity",
+            )
+            .into());
+        }
+        self.0.extract_features_simple(
+            Tolerance::PPM(mz_tolerance_ppm),
+            min_length,
+            max_gap,
+            None,
+        )?;
+        let Some(features) = self.0.features.as_ref() else {
+            return Ok(Vec::new());
+        };
+        Ok(features
+            .iter()
+            .map(|feature| {
+                let mut out = ffi::IonMobilityFeature {
+                    mz: feature.mz(),
+                    apex_ion_mobility: feature.apex_time().unwrap_or_default(),
+                    intensity: feature.intensity(),
+                    ion_mobility: Vec::with_capacity(feature.len()),
+                    mzs: Vec::with_capacity(feature.len()),
+                    intensities: Vec::with_capacity(feature.len()),
+                };
+                for (mz, im, int) in feature.iter() {
+                    out.mzs.push(mz);
+                    out.ion_mobility.push(im);
+                    out.intensities.push(int);
+                }
+                out
+            })
+            .collect())
+    }
+
+    pub fn ion_mobility_dimension_view(&self) -> &[f64] {
+        self.0
+            .arrays
+            .as_ref()
+            .map(|arrays| arrays.ion_mobility_dimension.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn arrays_at_mut(&mut self, ion_mobility_index: usize) -> Result<&mut BinaryArrayMap, Error> {
+        self.0
+            .arrays
+            .as_mut()
+            .and_then(|maps| maps.arrays.get_mut(ion_mobility_index))
+            .ok_or_else(|| {
+                Error::new(
+                    ffi::ErrorKind::IndexOutOfRange,
+                    format!("ion mobility index {ion_mobility_index} not found"),
+                )
+            })
+    }
+
+    /// Borrow the m/z array of one ion mobility slice, decoding it in place on first access
+    pub fn mzs_view_at(&mut self, ion_mobility_index: usize) -> Result<&[f64], BridgeError> {
+        Ok(view_array(
+            self.arrays_at_mut(ion_mobility_index)?,
+            &ArrayTypeImpl::MZArray,
+            BinaryDataArrayTypeImpl::Float64,
+        )?)
+    }
+
+    /// Borrow the intensity array of one ion mobility slice, decoding it in place on first access
+    pub fn intensities_view_at(
+        &mut self,
+        ion_mobility_index: usize,
+    ) -> Result<&[f32], BridgeError> {
+        Ok(view_array(
+            self.arrays_at_mut(ion_mobility_index)?,
+            &ArrayTypeImpl::IntensityArray,
+            BinaryDataArrayTypeImpl::Float32,
+        )?)
+    }
+
+    /// Combine the summaries of every ion mobility slice
+    fn summary(&self) -> SpectrumSummary {
+        let mut summary = SpectrumSummary::default();
+        let Some(maps) = self.0.arrays.as_ref() else {
+            return summary;
+        };
+        for (_, arrays) in maps.iter() {
+            let part = RefPeakDataLevel::<CentroidPeak, DeconvolutedPeak>::RawData(arrays)
+                .fetch_summaries();
+            if part.is_empty() {
+                continue;
+            }
+            if summary.is_empty() {
+                summary.mz_range = part.mz_range;
+            } else {
+                summary.mz_range.0 = summary.mz_range.0.min(part.mz_range.0);
+                summary.mz_range.1 = summary.mz_range.1.max(part.mz_range.1);
+            }
+            if part.base_peak.intensity > summary.base_peak.intensity {
+                summary.base_peak = part.base_peak;
+            }
+            summary.tic += part.tic;
+            summary.count += part.count;
+        }
+        summary
+    }
+
+    summary_methods!();
+
+    pub fn precursor(&self) -> Result<Box<Precursor<'_>>, BridgeError> {
+        option_box_or_err!(self.0.precursor().map(Precursor), "No precursor found")
+    }
+
+    pub fn precursor_count(&self) -> usize {
+        self.0.precursor_iter().count()
+    }
+
+    pub fn precursor_at(&self, index: usize) -> Result<Box<Precursor<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.precursor_iter().nth(index).map(Precursor),
+            IndexOutOfRange,
+            format!("precursor {index} not found")
+        )
+    }
+
+    pub fn acquisition(&self) -> Box<Acquisition<'_>> {
+        Box::new(Acquisition(self.0.acquisition()))
+    }
+
+    pub fn ion_mobility_range(&self, low: &mut f64, high: &mut f64) -> bool {
+        let dim = self.ion_mobility_dimension_view();
+        if dim.is_empty() {
+            return false;
+        }
+        let (lo, hi) = dim
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), val| {
+                (lo.min(*val), hi.max(*val))
+            });
+        *low = lo;
+        *high = hi;
+        true
+    }
+
+    pub fn ion_mobility_unit_name(&self) -> &'static str {
+        self.0.ion_mobility_unit().for_param().1
+    }
+
+    pub fn ion_mobility_unit_curie(&self, value: &mut ffi::CURIE) -> bool {
+        if let Some(curie) = self.0.ion_mobility_unit().to_curie() {
+            *value = curie.into();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Infer the kind of ion mobility measured from the ion mobility array, falling
+    /// back to the ion mobility param of the first scan
+    pub fn ion_mobility_type(&self) -> ffi::IonMobilityType {
+        let unit = self.0.ion_mobility_unit();
+        let from_array = self
+            .0
+            .arrays
+            .as_ref()
+            .map(|arrays| match arrays.ion_mobility_type {
+                ArrayTypeImpl::MeanDriftTimeArray
+                | ArrayTypeImpl::RawDriftTimeArray
+                | ArrayTypeImpl::DeconvolutedDriftTimeArray => ffi::IonMobilityType::DriftTime,
+                ArrayTypeImpl::MeanInverseReducedIonMobilityArray
+                | ArrayTypeImpl::RawInverseReducedIonMobilityArray
+                | ArrayTypeImpl::DeconvolutedInverseReducedIonMobilityArray => {
+                    ffi::IonMobilityType::InverseReducedIonMobility
+                }
+                _ => match unit {
+                    Unit::VoltSecondPerSquareCentimeter => {
+                        ffi::IonMobilityType::InverseReducedIonMobility
+                    }
+                    Unit::Millisecond => ffi::IonMobilityType::DriftTime,
+                    Unit::Volt => ffi::IonMobilityType::CompensationVoltage,
+                    _ => ffi::IonMobilityType::Unknown,
+                },
+            })
+            .unwrap_or(ffi::IonMobilityType::Unknown);
+        if from_array != ffi::IonMobilityType::Unknown {
+            return from_array;
+        }
+
+        let Some(scan) = self.0.acquisition().first_scan() else {
+            return ffi::IonMobilityType::Unknown;
+        };
+        [
+            (
+                curie!(MS:1002815),
+                ffi::IonMobilityType::InverseReducedIonMobility,
+            ),
+            (curie!(MS:1002476), ffi::IonMobilityType::DriftTime),
+            (
+                curie!(MS:1001581),
+                ffi::IonMobilityType::CompensationVoltage,
+            ),
+        ]
+        .into_iter()
+        .find(|(curie, _)| scan.get_param_by_curie(curie).is_some())
+        .map(|(_, kind)| kind)
+        .unwrap_or(ffi::IonMobilityType::Unknown)
+    }
+
+    /// Look up a named param on the frame, then on its first scan
+    fn frame_param(&self, name: &str) -> Option<f64> {
+        self.0
+            .get_param_by_name(name)
+            .or_else(|| {
+                self.0
+                    .acquisition()
+                    .first_scan()
+                    .and_then(|scan| scan.get_param_by_name(name))
+            })
+            .and_then(|param| param.to_f64().ok())
+    }
+
+    /// The ion accumulation time in milliseconds. Readers that do not write an explicit
+    /// param, like the TDF reader, record it as the first scan's injection time.
+    pub fn accumulation_time(&self, value: &mut f64) -> bool {
+        if let Some(val) = self.frame_param("accumulation time") {
+            *value = val;
+            return true;
+        }
+        match self.0.acquisition().first_scan() {
+            Some(scan) if scan.injection_time > 0.0 => {
+                *value = scan.injection_time as f64;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    param_methods!();
+}
+
+#[derive(Debug, Clone)]
+pub struct Chromatogram(ChromatogramImpl);
+
+impl Chromatogram {
+    pub fn id(&self) -> &str {
+        self.0.id()
     }
 
     pub fn index(&self) -> usize {
         self.0.index()
     }
 
-    pub fn start_time(&self) -> f64 {
-        self.0.start_time()
+    pub fn chromatogram_type(&self) -> ffi::ChromatogramType {
+        self.0.chromatogram_type().into()
+    }
+
+    pub fn ms_level(&self, value: &mut u8) -> bool {
+        option_bool!(self.0.ms_level(), value)
+    }
+
+    pub fn is_aggregate(&self) -> bool {
+        self.0.is_aggregate()
+    }
+
+    pub fn time_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> bool {
+        if let Ok(times) = self.0.time() {
+            for t in times.iter().copied() {
+                container.as_mut().push(t);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn intensity_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> bool {
+        if let Ok(ints) = self.0.intensity() {
+            for int in ints.iter().copied() {
+                container.as_mut().push(int);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn precursor(&self) -> Result<Box<Precursor<'_>>, BridgeError> {
+        option_box_or_err!(self.0.precursor().map(Precursor), "No precursor found")
+    }
+
+    fn raw_arrays(&self) -> Option<&BinaryArrayMap> {
+        Some(&self.0.arrays)
+    }
+
+    array_methods!();
+
+    param_methods!();
+}
+
+fn sorted_arrays(arrays: &BinaryArrayMap) -> Vec<&DataArrayImpl> {
+    let mut arrays: Vec<_> = arrays.iter().map(|(_, array)| array).collect();
+    arrays.sort_by(|a, b| a.name.cmp(&b.name));
+    arrays
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DataArray<'a>(&'a DataArrayImpl);
+
+impl DataArray<'_> {
+    pub fn array_type(&self) -> ffi::ArrayType {
+        (&self.0.name).into()
+    }
+
+    pub fn name(&self) -> String {
+        match &self.0.name {
+            ArrayTypeImpl::NonStandardDataArray { name } => name.to_string(),
+            array_type => array_type.as_param(None).name,
+        }
+    }
+
+    pub fn unit_name(&self) -> &'static str {
+        self.0.unit.for_param().1
+    }
+
+    pub fn unit_curie(&self, value: &mut ffi::CURIE) -> bool {
+        if let Some(curie) = self.0.unit.to_curie() {
+            *value = curie.into();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn dtype(&self) -> ffi::BinaryDataArrayType {
+        self.0.dtype.into()
+    }
+
+    pub fn compression(&self) -> ffi::BinaryCompressionType {
+        self.0.compression.into()
+    }
+
+    /// The number of values in the array, failing if the array cannot be decoded
+    pub fn len(&self) -> Result<usize, BridgeError> {
+        Ok(self.0.data_len()?)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, BridgeError> {
+        Ok(self.len()? == 0)
+    }
+
+    pub fn f32_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> Result<(), BridgeError> {
+        for val in self.0.to_f32()?.iter() {
+            container.as_mut().push(*val);
+        }
+        Ok(())
+    }
+
+    pub fn f64_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> Result<(), BridgeError> {
+        for val in self.0.to_f64()?.iter() {
+            container.as_mut().push(*val);
+        }
+        Ok(())
+    }
+
+    pub fn i32_into(&self, mut container: Pin<&mut CxxVector<i32>>) -> Result<(), BridgeError> {
+        for val in self.0.to_i32()?.iter() {
+            container.as_mut().push(*val);
+        }
+        Ok(())
+    }
+
+    pub fn i64_into(&self, mut container: Pin<&mut CxxVector<i64>>) -> Result<(), BridgeError> {
+        for val in self.0.to_i64()?.iter() {
+            container.as_mut().push(*val);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param(ParamImpl);
+
+impl ParamValue for Param {
+    fn is_empty(&self) -> bool {
+        <ParamImpl as ParamValue>::is_empty(&self.0)
+    }
+
+    fn is_i64(&self) -> bool {
+        <ParamImpl as ParamValue>::is_i64(&self.0)
+    }
+
+    fn is_f64(&self) -> bool {
+        <ParamImpl as ParamValue>::is_f64(&self.0)
+    }
+
+    fn is_buffer(&self) -> bool {
+        <ParamImpl as ParamValue>::is_buffer(&self.0)
+    }
+
+    fn is_str(&self) -> bool {
+        <ParamImpl as ParamValue>::is_str(&self.0)
+    }
+
+    fn to_f64(&self) -> Result<f64, ParamValueParseError> {
+        <ParamImpl as ParamValue>::to_f64(&self.0)
+    }
+
+    fn to_i64(&self) -> Result<i64, ParamValueParseError> {
+        <ParamImpl as ParamValue>::to_i64(&self.0)
+    }
+
+    fn to_str(&self) -> std::borrow::Cow<'_, str> {
+        <ParamImpl as ParamValue>::to_str(&self.0)
+    }
+
+    fn to_buffer(&self) -> Result<std::borrow::Cow<'_, [u8]>, ParamValueParseError> {
+        <ParamImpl as ParamValue>::to_buffer(&self.0)
+    }
+
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, T::Err> {
+        <ParamImpl as ParamValue>::parse(&self.0)
+    }
+
+    fn as_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        <ParamImpl as ParamValue>::as_bytes(&self.0)
+    }
+
+    fn as_ref(&self) -> mzdata::params::ValueRef<'_> {
+        <ParamImpl as ParamValue>::as_ref(&self.0)
+    }
+
+    fn data_len(&self) -> usize {
+        <ParamImpl as ParamValue>::data_len(&self.0)
+    }
+
+    fn is_boolean(&self) -> bool {
+        <ParamImpl as ParamValue>::is_boolean(&self.0)
+    }
+
+    fn to_bool(&self) -> Result<bool, ParamValueParseError> {
+        <ParamImpl as ParamValue>::to_bool(&self.0)
+    }
+}
+
+impl ParamLike for Param {
+    fn name(&self) -> &str {
+        <ParamImpl as ParamLike>::name(&self.0)
+    }
+
+    fn value(&self) -> mzdata::params::ValueRef<'_> {
+        <ParamImpl as ParamLike>::value(&self.0)
+    }
+
+    fn accession(&self) -> Option<mzdata::params::AccessionIntCode> {
+        <ParamImpl as ParamLike>::accession(&self.0)
+    }
+
+    fn controlled_vocabulary(&self) -> Option<mzdata::params::ControlledVocabulary> {
+        <ParamImpl as ParamLike>::controlled_vocabulary(&self.0)
+    }
+
+    fn unit(&self) -> mzdata::params::Unit {
+        <ParamImpl as ParamLike>::unit(&self.0)
+    }
+}
+
+impl Param {
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    pub fn curie(&self, value: &mut ffi::CURIE) -> bool {
+        if let Some(val) = self.0.curie() {
+            *value = ffi::CURIE::from(val);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_controlled(&self) -> bool {
+        self.0.is_controlled()
+    }
+
+    pub fn controlled_vocabulary(&self, value: &mut ffi::ControlledVocabulary) -> bool {
+        match self.0.controlled_vocabulary.map(|c| c.into()) {
+            Some(x) => {
+                *value = x;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn to_bool(&self, value: &mut bool) -> bool {
+        result_bool!(self.0.to_bool(), value);
+    }
+
+    pub fn to_str(&self, value: Pin<&mut CxxString>) -> bool {
+        let out = self.0.to_string();
+        value.push_str(&out);
+        true
+    }
+
+    pub fn to_f64(&self, value: &mut f64) -> bool {
+        result_bool!(self.0.to_f64(), value);
+    }
+
+    pub fn to_i64(&self, value: &mut i64) -> bool {
+        result_bool!(self.0.to_i64(), value);
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IsolationWindow(IsolationWindowImpl);
+
+impl IsolationWindow {
+    pub fn contains(&self, point: f32) -> bool {
+        let (lower_bound, upper_bound) = self.bounds();
+        lower_bound <= point && point <= upper_bound
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn target(&self) -> f32 {
+        self.0.target
+    }
+
+    /// Whether the window records any bounds, rather than only a target m/z
+    pub fn has_bounds(&self) -> bool {
+        self.0.lower_bound != 0.0 || self.0.upper_bound != 0.0
+    }
+
+    /// The m/z bounds, resolving windows only recorded as offsets from the target and
+    /// collapsing windows without bounds onto the target
+    fn bounds(&self) -> (f32, f32) {
+        if !self.has_bounds() {
+            return (self.0.target, self.0.target);
+        }
+        match self.0.flags {
+            IsolationWindowStateImpl::Offset => (
+                (self.0.target - self.0.lower_bound).max(0.0),
+                self.0.target + self.0.upper_bound,
+            ),
+            _ => (self.0.lower_bound, self.0.upper_bound),
+        }
+    }
+
+    pub fn lower_bound(&self) -> f32 {
+        self.bounds().0
+    }
+
+    pub fn upper_bound(&self) -> f32 {
+        self.bounds().1
+    }
+
+    pub fn lower_offset(&self) -> f32 {
+        match self.0.flags {
+            IsolationWindowStateImpl::Offset => self.0.lower_bound,
+            _ => self.0.target - self.lower_bound(),
+        }
+    }
+
+    pub fn upper_offset(&self) -> f32 {
+        match self.0.flags {
+            IsolationWindowStateImpl::Offset => self.0.upper_bound,
+            _ => self.upper_bound() - self.0.target,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        let (lower_bound, upper_bound) = self.bounds();
+        upper_bound - lower_bound
+    }
+
+    /// How the bounds were established from the source file
+    pub fn state(&self) -> ffi::IsolationWindowState {
+        self.0.flags.into()
+    }
+
+    fn as_dia_window(&self) -> ffi::DIAWindow {
+        ffi::DIAWindow {
+            target: self.target(),
+            lower_bound: self.lower_bound(),
+            upper_bound: self.upper_bound(),
+            state: self.state(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunMetadata(FileMetadataConfig);
+
+impl RunMetadata {
+    pub fn run_id(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.run_description().and_then(|r| r.id.as_ref()) {
+            out.as_mut().clear();
+            out.as_mut().push_str(val);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn start_timestamp(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.run_description().and_then(|r| r.start_time) {
+            out.as_mut().clear();
+            out.as_mut().push_str(&val.to_rfc3339());
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn default_instrument_configuration_id(&self, value: &mut u32) -> bool {
+        option_bool!(
+            self.0
+                .run_description()
+                .and_then(|r| r.default_instrument_id),
+            value
+        )
+    }
+
+    pub fn spectrum_count_hint(&self, value: &mut u64) -> bool {
+        option_bool!(self.0.spectrum_count_hint(), value)
+    }
+
+    pub fn file_contents(&self) -> Vec<Param> {
+        self.0
+            .file_description()
+            .contents
+            .iter()
+            .cloned()
+            .map(Param)
+            .collect()
+    }
+
+    pub fn instrument_configuration_ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self.0.instrument_configurations().keys().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn instrument_configuration(
+        &self,
+        id: u32,
+    ) -> Result<Box<InstrumentConfiguration<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0
+                .instrument_configurations()
+                .get(&id)
+                .map(InstrumentConfiguration),
+            format!("instrument configuration {id} not found")
+        )
+    }
+
+    pub fn software_count(&self) -> usize {
+        self.0.softwares().len()
+    }
+
+    pub fn software(&self, index: usize) -> Result<Box<Software<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.softwares().get(index).map(Software),
+            IndexOutOfRange,
+            format!("software {index} not found")
+        )
+    }
+
+    pub fn data_processing_count(&self) -> usize {
+        self.0.data_processings().len()
+    }
+
+    pub fn data_processing(&self, index: usize) -> Result<Box<DataProcessing<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.data_processings().get(index).map(DataProcessing),
+            IndexOutOfRange,
+            format!("data processing {index} not found")
+        )
+    }
+
+    pub fn source_file_count(&self) -> usize {
+        self.0.file_description().source_files.len()
     }
 
-    pub fn ms_level(&self) -> u8 {
-        self.0.ms_level()
+    pub fn source_file(&self, index: usize) -> Result<Box<SourceFile<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0
+                .file_description()
+                .source_files
+                .get(index)
+                .map(SourceFile),
+            IndexOutOfRange,
+            format!("source file {index} not found")
+        )
     }
 
-    pub fn is_profile(&self) -> bool {
-        matches!(
-            self.0.signal_continuity(),
-            mzdata::spectrum::SignalContinuity::Profile
+    pub fn sample_count(&self) -> usize {
+        self.0.samples().len()
+    }
+
+    pub fn sample(&self, index: usize) -> Result<Box<Sample<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.samples().get(index).map(Sample),
+            IndexOutOfRange,
+            format!("sample {index} not found")
         )
     }
+}
 
-    pub fn ion_mobility_dimension(&self, mut out: Pin<&mut CxxVector<f64>>) -> bool {
-        self.0
-            .arrays
-            .as_ref()
-            .map(|arrays| {
-                let vals = arrays.ion_mobility_dimension.as_slice();
-                for val in vals {
-                    out.as_mut().push(*val);
-                }
-                true
-            })
-            .unwrap_or_default()
+#[derive(Debug, Clone)]
+pub struct InstrumentConfiguration<'a>(&'a InstrumentConfigurationImpl);
+
+impl InstrumentConfiguration<'_> {
+    pub fn id(&self) -> u32 {
+        self.0.id
     }
 
-    pub fn signal_at_ion_mobility_index_into(
-        &self,
-        ion_mobility_index: usize,
-        mut mzs_container: Pin<&mut CxxVector<f64>>,
-        mut intensities_container: Pin<&mut CxxVector<f32>>,
-        ion_mobility: &mut f64,
-    ) {
-        if let Some(maps) = self.0.arrays.as_ref() {
-            if let Some(val) = maps.ion_mobility_dimension.get(ion_mobility_index) {
-                *ion_mobility = *val;
-            }
-            if let Some(arrays) = maps.arrays.get(ion_mobility_index) {
-                if let Some(mzs) = arrays.mzs().ok() {
-                    for mz in mzs.iter().copied() {
-                        mzs_container.as_mut().push(mz);
-                    }
-                }
-                if let Some(ints) = arrays.intensities().ok() {
-                    for int in ints.iter().copied() {
-                        intensities_container.as_mut().push(int);
-                    }
-                }
-            }
-        }
+    pub fn software_reference(&self) -> &str {
+        &self.0.software_reference
     }
 
-    pub fn precursor(&self) -> Result<Box<Precursor<'_>>, String> {
-        option_box_or_err!(self.0.precursor().map(Precursor), "No precursor found")
+    pub fn len(&self) -> usize {
+        self.0.len()
     }
 
-    pub fn acquisition(&self) -> Box<Acquisition<'_>> {
-        Box::new(Acquisition(self.0.acquisition()))
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn component(&self, index: usize) -> Result<Box<InstrumentComponent<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.components.get(index).map(InstrumentComponent),
+            IndexOutOfRange,
+            format!("component {index} not found")
+        )
     }
 
     param_methods!();
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Param(ParamImpl);
+#[derive(Debug, Clone)]
+pub struct InstrumentComponent<'a>(&'a ComponentImpl);
 
-impl ParamValue for Param {
-    fn is_empty(&self) -> bool {
-        <ParamImpl as ParamValue>::is_empty(&self.0)
+impl InstrumentComponent<'_> {
+    pub fn component_type(&self) -> ffi::ComponentType {
+        self.0.component_type.into()
     }
 
-    fn is_i64(&self) -> bool {
-        <ParamImpl as ParamValue>::is_i64(&self.0)
+    pub fn order(&self) -> u8 {
+        self.0.order
     }
 
-    fn is_f64(&self) -> bool {
-        <ParamImpl as ParamValue>::is_f64(&self.0)
+    pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.name() {
+            out.as_mut().clear();
+            out.as_mut().push_str(val);
+            true
+        } else {
+            false
+        }
     }
 
-    fn is_buffer(&self) -> bool {
-        <ParamImpl as ParamValue>::is_buffer(&self.0)
+    param_methods!();
+}
+
+#[derive(Debug, Clone)]
+pub struct Software<'a>(&'a SoftwareImpl);
+
+impl Software<'_> {
+    pub fn id(&self) -> &str {
+        &self.0.id
     }
 
-    fn is_str(&self) -> bool {
-        <ParamImpl as ParamValue>::is_str(&self.0)
+    pub fn version(&self) -> &str {
+        &self.0.version
     }
 
-    fn to_f64(&self) -> Result<f64, ParamValueParseError> {
-        <ParamImpl as ParamValue>::to_f64(&self.0)
+    pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(term) = self.0.find_software_term() {
+            out.as_mut().clear();
+            out.as_mut().push_str(term.name());
+            true
+        } else {
+            false
+        }
     }
 
-    fn to_i64(&self) -> Result<i64, ParamValueParseError> {
-        <ParamImpl as ParamValue>::to_i64(&self.0)
+    pub fn is_analysis(&self) -> bool {
+        self.0.is_analysis()
     }
 
-    fn to_str(&self) -> std::borrow::Cow<'_, str> {
-        <ParamImpl as ParamValue>::to_str(&self.0)
+    pub fn is_data_processing(&self) -> bool {
+        self.0.is_data_processing()
     }
 
-    fn to_buffer(&self) -> Result<std::borrow::Cow<'_, [u8]>, ParamValueParseError> {
-        <ParamImpl as ParamValue>::to_buffer(&self.0)
+    pub fn is_acquisition(&self) -> bool {
+        self.0.is_acquisition()
     }
 
-    fn parse<T: std::str::FromStr>(&self) -> Result<T, T::Err> {
-        <ParamImpl as ParamValue>::parse(&self.0)
+    param_methods!();
+}
+
+#[derive(Debug, Clone)]
+pub struct DataProcessing<'a>(&'a DataProcessingImpl);
+
+impl DataProcessing<'_> {
+    pub fn id(&self) -> &str {
+        &self.0.id
     }
 
-    fn as_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
-        <ParamImpl as ParamValue>::as_bytes(&self.0)
+    pub fn len(&self) -> usize {
+        self.0.len()
     }
 
-    fn as_ref(&self) -> mzdata::params::ValueRef<'_> {
-        <ParamImpl as ParamValue>::as_ref(&self.0)
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
     }
 
-    fn data_len(&self) -> usize {
-        <ParamImpl as ParamValue>::data_len(&self.0)
+    pub fn method(&self, index: usize) -> Result<Box<ProcessingMethod<'_>>, BridgeError> {
+        option_box_or_err!(
+            self.0.methods.get(index).map(ProcessingMethod),
+            IndexOutOfRange,
+            format!("processing method {index} not found")
+        )
     }
+}
 
-    fn is_boolean(&self) -> bool {
-        <ParamImpl as ParamValue>::is_boolean(&self.0)
+#[derive(Debug, Clone)]
+pub struct ProcessingMethod<'a>(&'a ProcessingMethodImpl);
+
+impl ProcessingMethod<'_> {
+    pub fn order(&self) -> i8 {
+        self.0.order
     }
 
-    fn to_bool(&self) -> Result<bool, ParamValueParseError> {
-        <ParamImpl as ParamValue>::to_bool(&self.0)
+    pub fn software_reference(&self) -> &str {
+        &self.0.software_reference
     }
+
+    param_methods!();
 }
 
-impl ParamLike for Param {
-    fn name(&self) -> &str {
-        <ParamImpl as ParamLike>::name(&self.0)
+#[derive(Debug, Clone)]
+pub struct SourceFile<'a>(&'a SourceFileImpl);
+
+impl SourceFile<'_> {
+    pub fn id(&self) -> &str {
+        &self.0.id
     }
 
-    fn value(&self) -> mzdata::params::ValueRef {
-        <ParamImpl as ParamLike>::value(&self.0)
+    pub fn name(&self) -> &str {
+        &self.0.name
     }
 
-    fn accession(&self) -> Option<mzdata::params::AccessionIntCode> {
-        <ParamImpl as ParamLike>::accession(&self.0)
+    pub fn location(&self) -> &str {
+        &self.0.location
     }
 
-    fn controlled_vocabulary(&self) -> Option<mzdata::params::ControlledVocabulary> {
-        <ParamImpl as ParamLike>::controlled_vocabulary(&self.0)
+    pub fn file_format(&self) -> Result<Box<Param>, BridgeError> {
+        option_box_or_err!(
+            self.0.file_format.clone().map(Param),
+            "No file format found"
+        )
     }
 
-    fn unit(&self) -> mzdata::params::Unit {
-        <ParamImpl as ParamLike>::unit(&self.0)
+    pub fn id_format(&self) -> Result<Box<Param>, BridgeError> {
+        option_box_or_err!(self.0.id_format.clone().map(Param), "No ID format found")
     }
+
+    param_methods!();
 }
 
-impl Param {
-    pub fn name(&self) -> &str {
-        self.0.name()
+#[derive(Debug, Clone)]
+pub struct Sample<'a>(&'a SampleImpl);
+
+impl Sample<'_> {
+    pub fn id(&self) -> &str {
+        &self.0.id
     }
 
-    pub fn curie(&self, value: &mut ffi::CURIE) -> bool {
-        if let Some(val) = self.0.curie() {
-            *value = ffi::CURIE::from(val);
+    pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool {
+        if let Some(val) = self.0.name.as_ref() {
+            out.as_mut().clear();
+            out.as_mut().push_str(val);
             true
         } else {
             false
         }
     }
 
-    pub fn is_controlled(&self) -> bool {
-        self.0.is_controlled()
+    param_methods!();
+}
+
+impl From<ffi::ControlledVocabulary> for ControlledVocabularyImpl {
+    fn from(value: ffi::ControlledVocabulary) -> Self {
+        match value {
+            ffi::ControlledVocabulary::MS => Self::MS,
+            ffi::ControlledVocabulary::UO => Self::UO,
+            ffi::ControlledVocabulary::EFO => Self::EFO,
+            ffi::ControlledVocabulary::OBI => Self::OBI,
+            ffi::ControlledVocabulary::HANCESTRO => Self::HANCESTRO,
+            ffi::ControlledVocabulary::BFO => Self::BFO,
+            ffi::ControlledVocabulary::NCIT => Self::NCIT,
+            ffi::ControlledVocabulary::BTO => Self::BTO,
+            ffi::ControlledVocabulary::PRIDE => Self::PRIDE,
+            ffi::ControlledVocabulary::Unknown => Self::Unknown,
+            _ => Self::Unknown,
+        }
     }
+}
 
-    pub fn controlled_vocabulary(&self, value: &mut ffi::ControlledVocabulary) -> bool {
-        match self.0.controlled_vocabulary.map(|c| c.into()) {
-            Some(x) => {
-                *value = x;
-                true
+impl From<ControlledVocabularyImpl> for ffi::ControlledVocabulary {
+    fn from(value: ControlledVocabularyImpl) -> ffi::ControlledVocabulary {
+        match value {
+            ControlledVocabularyImpl::MS => ffi::ControlledVocabulary::MS,
+            ControlledVocabularyImpl::UO => ffi::ControlledVocabulary::UO,
+            ControlledVocabularyImpl::EFO => ffi::ControlledVocabulary::EFO,
+            ControlledVocabularyImpl::OBI => ffi::ControlledVocabulary::OBI,
+            ControlledVocabularyImpl::HANCESTRO => ffi::ControlledVocabulary::HANCESTRO,
+            ControlledVocabularyImpl::BFO => ffi::ControlledVocabulary::BFO,
+            ControlledVocabularyImpl::NCIT => ffi::ControlledVocabulary::NCIT,
+            ControlledVocabularyImpl::BTO => ffi::ControlledVocabulary::BTO,
+            ControlledVocabularyImpl::PRIDE => ffi::ControlledVocabulary::PRIDE,
+            ControlledVocabularyImpl::Unknown => ffi::ControlledVocabulary::Unknown,
+        }
+    }
+}
+
+impl From<ffi::MassSpectrometryFormat> for MassSpectrometryFormatImpl {
+    fn from(value: ffi::MassSpectrometryFormat) -> Self {
+        match value {
+            ffi::MassSpectrometryFormat::MGF => Self::MGF,
+            ffi::MassSpectrometryFormat::MzML => Self::MzML,
+            ffi::MassSpectrometryFormat::MzMLb => Self::MzMLb,
+            ffi::MassSpectrometryFormat::ThermoRaw => Self::ThermoRaw,
+            ffi::MassSpectrometryFormat::BrukerTDF => Self::BrukerTDF,
+            ffi::MassSpectrometryFormat::Unknown => Self::Unknown,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl From<MassSpectrometryFormatImpl> for ffi::MassSpectrometryFormat {
+    fn from(value: MassSpectrometryFormatImpl) -> ffi::MassSpectrometryFormat {
+        match value {
+            MassSpectrometryFormatImpl::MGF => ffi::MassSpectrometryFormat::MGF,
+            MassSpectrometryFormatImpl::MzML => ffi::MassSpectrometryFormat::MzML,
+            MassSpectrometryFormatImpl::MzMLb => ffi::MassSpectrometryFormat::MzMLb,
+            MassSpectrometryFormatImpl::ThermoRaw => ffi::MassSpectrometryFormat::ThermoRaw,
+            MassSpectrometryFormatImpl::BrukerTDF => ffi::MassSpectrometryFormat::BrukerTDF,
+            MassSpectrometryFormatImpl::Unknown => ffi::MassSpectrometryFormat::Unknown,
+            _ => ffi::MassSpectrometryFormat::Unknown,
+        }
+    }
+}
+
+impl From<ComponentTypeImpl> for ffi::ComponentType {
+    fn from(value: ComponentTypeImpl) -> ffi::ComponentType {
+        match value {
+            ComponentTypeImpl::Analyzer => ffi::ComponentType::Analyzer,
+            ComponentTypeImpl::IonSource => ffi::ComponentType::IonSource,
+            ComponentTypeImpl::Detector => ffi::ComponentType::Detector,
+            ComponentTypeImpl::Unknown => ffi::ComponentType::Unknown,
+        }
+    }
+}
+
+impl From<ChromatogramTypeImpl> for ffi::ChromatogramType {
+    fn from(value: ChromatogramTypeImpl) -> ffi::ChromatogramType {
+        match value {
+            ChromatogramTypeImpl::Unknown => ffi::ChromatogramType::Unknown,
+            ChromatogramTypeImpl::TotalIonCurrentChromatogram => {
+                ffi::ChromatogramType::TotalIonCurrentChromatogram
+            }
+            ChromatogramTypeImpl::BasePeakChromatogram => {
+                ffi::ChromatogramType::BasePeakChromatogram
+            }
+            ChromatogramTypeImpl::SelectedIonCurrentChromatogram => {
+                ffi::ChromatogramType::SelectedIonCurrentChromatogram
+            }
+            ChromatogramTypeImpl::SelectedIonMonitoringChromatogram => {
+                ffi::ChromatogramType::SelectedIonMonitoringChromatogram
+            }
+            ChromatogramTypeImpl::SelectedReactionMonitoringChromatogram => {
+                ffi::ChromatogramType::SelectedReactionMonitoringChromatogram
+            }
+            ChromatogramTypeImpl::AbsorptionChromatogram => {
+                ffi::ChromatogramType::AbsorptionChromatogram
+            }
+            ChromatogramTypeImpl::EmissionChromatogram => {
+                ffi::ChromatogramType::EmissionChromatogram
+            }
+            ChromatogramTypeImpl::FlowRateChromatogram => {
+                ffi::ChromatogramType::FlowRateChromatogram
+            }
+            ChromatogramTypeImpl::PressureChromatogram => {
+                ffi::ChromatogramType::PressureChromatogram
+            }
+            ChromatogramTypeImpl::TemperatureChromatogram => {
+                ffi::ChromatogramType::TemperatureChromatogram
             }
-            None => false,
         }
     }
+}
 
-    pub fn to_bool(&self, value: &mut bool) -> bool {
-        result_bool!(self.0.to_bool(), value);
+impl From<&ArrayTypeImpl> for ffi::ArrayType {
+    fn from(value: &ArrayTypeImpl) -> ffi::ArrayType {
+        match value {
+            ArrayTypeImpl::Unknown => ffi::ArrayType::Unknown,
+            ArrayTypeImpl::MZArray => ffi::ArrayType::MZArray,
+            ArrayTypeImpl::IntensityArray => ffi::ArrayType::IntensityArray,
+            ArrayTypeImpl::ChargeArray => ffi::ArrayType::ChargeArray,
+            ArrayTypeImpl::SignalToNoiseArray => ffi::ArrayType::SignalToNoiseArray,
+            ArrayTypeImpl::TimeArray => ffi::ArrayType::TimeArray,
+            ArrayTypeImpl::WavelengthArray => ffi::ArrayType::WavelengthArray,
+            ArrayTypeImpl::IonMobilityArray => ffi::ArrayType::IonMobilityArray,
+            ArrayTypeImpl::MeanIonMobilityArray => ffi::ArrayType::MeanIonMobilityArray,
+            ArrayTypeImpl::MeanDriftTimeArray => ffi::ArrayType::MeanDriftTimeArray,
+            ArrayTypeImpl::MeanInverseReducedIonMobilityArray => {
+                ffi::ArrayType::MeanInverseReducedIonMobilityArray
+            }
+            ArrayTypeImpl::RawIonMobilityArray => ffi::ArrayType::RawIonMobilityArray,
+            ArrayTypeImpl::RawDriftTimeArray => ffi::ArrayType::RawDriftTimeArray,
+            ArrayTypeImpl::RawInverseReducedIonMobilityArray => {
+                ffi::ArrayType::RawInverseReducedIonMobilityArray
+            }
+            ArrayTypeImpl::DeconvolutedIonMobilityArray => {
+                ffi::ArrayType::DeconvolutedIonMobilityArray
+            }
+            ArrayTypeImpl::DeconvolutedDriftTimeArray => ffi::ArrayType::DeconvolutedDriftTimeArray,
+            ArrayTypeImpl::DeconvolutedInverseReducedIonMobilityArray => {
+                ffi::ArrayType::DeconvolutedInverseReducedIonMobilityArray
+            }
+            ArrayTypeImpl::BaselineArray => ffi::ArrayType::BaselineArray,
+            ArrayTypeImpl::ResolutionArray => ffi::ArrayType::ResolutionArray,
+            ArrayTypeImpl::PressureArray => ffi::ArrayType::PressureArray,
+            ArrayTypeImpl::TemperatureArray => ffi::ArrayType::TemperatureArray,
+            ArrayTypeImpl::FlowRateArray => ffi::ArrayType::FlowRateArray,
+            ArrayTypeImpl::NonStandardDataArray { name: _ } => ffi::ArrayType::NonStandardDataArray,
+        }
     }
+}
 
-    pub fn to_str(&self, value: Pin<&mut CxxString>) -> bool {
-        let out = self.0.to_string();
-        value.push_str(&out);
-        true
+/// Non-standard arrays are identified by name, which the bridged enum cannot carry
+impl TryFrom<ffi::ArrayType> for ArrayTypeImpl {
+    type Error = ffi::ArrayType;
+
+    fn try_from(value: ffi::ArrayType) -> Result<Self, Self::Error> {
+        let array_type = match value {
+            ffi::ArrayType::Unknown => ArrayTypeImpl::Unknown,
+            ffi::ArrayType::MZArray => ArrayTypeImpl::MZArray,
+            ffi::ArrayType::IntensityArray => ArrayTypeImpl::IntensityArray,
+            ffi::ArrayType::ChargeArray => ArrayTypeImpl::ChargeArray,
+            ffi::ArrayType::SignalToNoiseArray => ArrayTypeImpl::SignalToNoiseArray,
+            ffi::ArrayType::TimeArray => ArrayTypeImpl::TimeArray,
+            ffi::ArrayType::WavelengthArray => ArrayTypeImpl::WavelengthArray,
+            ffi::ArrayType::IonMobilityArray => ArrayTypeImpl::IonMobilityArray,
+            ffi::ArrayType::MeanIonMobilityArray => ArrayTypeImpl::MeanIonMobilityArray,
+            ffi::ArrayType::MeanDriftTimeArray => ArrayTypeImpl::MeanDriftTimeArray,
+            ffi::ArrayType::MeanInverseReducedIonMobilityArray => {
+                ArrayTypeImpl::MeanInverseReducedIonMobilityArray
+            }
+            ffi::ArrayType::RawIonMobilityArray => ArrayTypeImpl::RawIonMobilityArray,
+            ffi::ArrayType::RawDriftTimeArray => ArrayTypeImpl::RawDriftTimeArray,
+            ffi::ArrayType::RawInverseReducedIonMobilityArray => {
+                ArrayTypeImpl::RawInverseReducedIonMobilityArray
+            }
+            ffi::ArrayType::DeconvolutedIonMobilityArray => {
+                ArrayTypeImpl::DeconvolutedIonMobilityArray
+            }
+            ffi::ArrayType::DeconvolutedDriftTimeArray => ArrayTypeImpl::DeconvolutedDriftTimeArray,
+            ffi::ArrayType::DeconvolutedInverseReducedIonMobilityArray => {
+                ArrayTypeImpl::DeconvolutedInverseReducedIonMobilityArray
+            }
+            ffi::ArrayType::BaselineArray => ArrayTypeImpl::BaselineArray,
+            ffi::ArrayType::ResolutionArray => ArrayTypeImpl::ResolutionArray,
+            ffi::ArrayType::PressureArray => ArrayTypeImpl::PressureArray,
+            ffi::ArrayType::TemperatureArray => ArrayTypeImpl::TemperatureArray,
+            ffi::ArrayType::FlowRateArray => ArrayTypeImpl::FlowRateArray,
+            _ => return Err(value),
+        };
+        Ok(array_type)
     }
+}
 
-    pub fn to_f64(&self, value: &mut f64) -> bool {
-        result_bool!(self.0.to_f64(), value);
+impl From<BinaryDataArrayTypeImpl> for ffi::BinaryDataArrayType {
+    fn from(value: BinaryDataArrayTypeImpl) -> ffi::BinaryDataArrayType {
+        match value {
+            BinaryDataArrayTypeImpl::Unknown => ffi::BinaryDataArrayType::Unknown,
+            BinaryDataArrayTypeImpl::Float64 => ffi::BinaryDataArrayType::Float64,
+            BinaryDataArrayTypeImpl::Float32 => ffi::BinaryDataArrayType::Float32,
+            BinaryDataArrayTypeImpl::Int64 => ffi::BinaryDataArrayType::Int64,
+            BinaryDataArrayTypeImpl::Int32 => ffi::BinaryDataArrayType::Int32,
+            BinaryDataArrayTypeImpl::ASCII => ffi::BinaryDataArrayType::ASCII,
+        }
     }
+}
 
-    pub fn to_i64(&self, value: &mut i64) -> bool {
-        result_bool!(self.0.to_i64(), value);
+impl From<BinaryCompressionTypeImpl> for ffi::BinaryCompressionType {
+    fn from(value: BinaryCompressionTypeImpl) -> ffi::BinaryCompressionType {
+        match value {
+            BinaryCompressionTypeImpl::NoCompression => ffi::BinaryCompressionType::NoCompression,
+            BinaryCompressionTypeImpl::Zlib => ffi::BinaryCompressionType::Zlib,
+            BinaryCompressionTypeImpl::NumpressLinear => ffi::BinaryCompressionType::NumpressLinear,
+            BinaryCompressionTypeImpl::NumpressSLOF => ffi::BinaryCompressionType::NumpressSLOF,
+            BinaryCompressionTypeImpl::NumpressPIC => ffi::BinaryCompressionType::NumpressPIC,
+            BinaryCompressionTypeImpl::NumpressLinearZlib => {
+                ffi::BinaryCompressionType::NumpressLinearZlib
+            }
+            BinaryCompressionTypeImpl::NumpressSLOFZlib => {
+                ffi::BinaryCompressionType::NumpressSLOFZlib
+            }
+            BinaryCompressionTypeImpl::NumpressPICZlib => {
+                ffi::BinaryCompressionType::NumpressPICZlib
+            }
+            BinaryCompressionTypeImpl::LinearPrediction => {
+                ffi::BinaryCompressionType::LinearPrediction
+            }
+            BinaryCompressionTypeImpl::DeltaPrediction => {
+                ffi::BinaryCompressionType::DeltaPrediction
+            }
+            BinaryCompressionTypeImpl::Decoded => ffi::BinaryCompressionType::Decoded,
+        }
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct IsolationWindow(IsolationWindowImpl);
-
-impl IsolationWindow {
-    pub fn contains(&self, point: f32) -> bool {
-        self.0.contains(point)
+impl From<IsolationWindowStateImpl> for ffi::IsolationWindowState {
+    fn from(value: IsolationWindowStateImpl) -> ffi::IsolationWindowState {
+        match value {
+            IsolationWindowStateImpl::Unknown => ffi::IsolationWindowState::Unknown,
+            IsolationWindowStateImpl::Offset => ffi::IsolationWindowState::Offset,
+            IsolationWindowStateImpl::Explicit => ffi::IsolationWindowState::Explicit,
+            IsolationWindowStateImpl::Complete => ffi::IsolationWindowState::Complete,
+        }
     }
+}
 
-    pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+impl From<HasIonMobility> for ffi::HasIonMobility {
+    fn from(value: HasIonMobility) -> ffi::HasIonMobility {
+        match value {
+            HasIonMobility::None => ffi::HasIonMobility::None,
+            HasIonMobility::Point => ffi::HasIonMobility::Point,
+            HasIonMobility::Dimension => ffi::HasIonMobility::Dimension,
+        }
     }
+}
 
-    pub fn target(&self) -> f32 {
-        self.0.target as f32
+impl From<ScanPolarityImpl> for ffi::ScanPolarity {
+    fn from(value: ScanPolarityImpl) -> ffi::ScanPolarity {
+        match value {
+            ScanPolarityImpl::Unknown => ffi::ScanPolarity::Unknown,
+            ScanPolarityImpl::Positive => ffi::ScanPolarity::Positive,
+            ScanPolarityImpl::Negative => ffi::ScanPolarity::Negative,
+        }
     }
+}
 
-    pub fn lower_bound(&self) -> f32 {
-        self.0.lower_bound
+impl From<ffi::ScanPolarity> for ScanPolarityImpl {
+    fn from(value: ffi::ScanPolarity) -> ScanPolarityImpl {
+        match value {
+            ffi::ScanPolarity::Positive => ScanPolarityImpl::Positive,
+            ffi::ScanPolarity::Negative => ScanPolarityImpl::Negative,
+            _ => ScanPolarityImpl::Unknown,
+        }
     }
+}
 
-    pub fn upper_bound(&self) -> f32 {
-        self.0.upper_bound
+impl From<SignalContinuityImpl> for ffi::SignalContinuity {
+    fn from(value: SignalContinuityImpl) -> ffi::SignalContinuity {
+        match value {
+            SignalContinuityImpl::Unknown => ffi::SignalContinuity::Unknown,
+            SignalContinuityImpl::Centroid => ffi::SignalContinuity::Centroid,
+            SignalContinuityImpl::Profile => ffi::SignalContinuity::Profile,
+        }
     }
 }
 
-impl From<ffi::ControlledVocabulary> for ControlledVocabularyImpl {
-    fn from(value: ffi::ControlledVocabulary) -> Self {
+impl From<ffi::SignalContinuity> for SignalContinuityImpl {
+    fn from(value: ffi::SignalContinuity) -> SignalContinuityImpl {
         match value {
-            ffi::ControlledVocabulary::MS => Self::MS,
-            ffi::ControlledVocabulary::UO => Self::UO,
-            ffi::ControlledVocabulary::EFO => Self::EFO,
-            ffi::ControlledVocabulary::OBI => Self::OBI,
-            ffi::ControlledVocabulary::HANCESTRO => Self::HANCESTRO,
-            ffi::ControlledVocabulary::BFO => Self::BFO,
-            ffi::ControlledVocabulary::NCIT => Self::NCIT,
-            ffi::ControlledVocabulary::BTO => Self::BTO,
-            ffi::ControlledVocabulary::PRIDE => Self::PRIDE,
-            ffi::ControlledVocabulary::Unknown => Self::Unknown,
-            _ => Self::Unknown,
+            ffi::SignalContinuity::Centroid => SignalContinuityImpl::Centroid,
+            ffi::SignalContinuity::Profile => SignalContinuityImpl::Profile,
+            _ => SignalContinuityImpl::Unknown,
         }
     }
 }
 
-impl From<ControlledVocabularyImpl> for ffi::ControlledVocabulary {
-    fn from(value: ControlledVocabularyImpl) -> ffi::ControlledVocabulary {
+impl From<ffi::PeakFitType> for PeakFitType {
+    fn from(value: ffi::PeakFitType) -> PeakFitType {
         match value {
-            ControlledVocabularyImpl::MS => ffi::ControlledVocabulary::MS,
-            ControlledVocabularyImpl::UO => ffi::ControlledVocabulary::UO,
-            ControlledVocabularyImpl::EFO => ffi::ControlledVocabulary::EFO,
-            ControlledVocabularyImpl::OBI => ffi::ControlledVocabulary::OBI,
-            ControlledVocabularyImpl::HANCESTRO => ffi::ControlledVocabulary::HANCESTRO,
-            ControlledVocabularyImpl::BFO => ffi::ControlledVocabulary::BFO,
-            ControlledVocabularyImpl::NCIT => ffi::ControlledVocabulary::NCIT,
-            ControlledVocabularyImpl::BTO => ffi::ControlledVocabulary::BTO,
-            ControlledVocabularyImpl::PRIDE => ffi::ControlledVocabulary::PRIDE,
-            ControlledVocabularyImpl::Unknown => ffi::ControlledVocabulary::Unknown,
+            ffi::PeakFitType::Apex => PeakFitType::Apex,
+            ffi::PeakFitType::Lorentzian => PeakFitType::Lorentzian,
+            _ => PeakFitType::Quadratic,
         }
     }
 }
@@ -695,12 +3462,255 @@ pub(crate) mod ffi {
         Unknown,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MassSpectrometryFormat {
+        MGF,
+        MzML,
+        MzMLb,
+        ThermoRaw,
+        BrukerTDF,
+        Unknown,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ErrorKind {
+        EndOfFile,
+        IndexOutOfRange,
+        NotFound,
+        IOError,
+        ParseError,
+        UnsupportedFormat,
+        ProcessingError,
+        Unknown,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ComponentType {
+        Analyzer,
+        IonSource,
+        Detector,
+        Unknown,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ChromatogramType {
+        Unknown,
+        TotalIonCurrentChromatogram,
+        BasePeakChromatogram,
+        SelectedIonCurrentChromatogram,
+        SelectedIonMonitoringChromatogram,
+        SelectedReactionMonitoringChromatogram,
+        AbsorptionChromatogram,
+        EmissionChromatogram,
+        FlowRateChromatogram,
+        PressureChromatogram,
+        TemperatureChromatogram,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ArrayType {
+        Unknown,
+        MZArray,
+        IntensityArray,
+        ChargeArray,
+        SignalToNoiseArray,
+        TimeArray,
+        WavelengthArray,
+        IonMobilityArray,
+        MeanIonMobilityArray,
+        MeanDriftTimeArray,
+        MeanInverseReducedIonMobilityArray,
+        RawIonMobilityArray,
+        RawDriftTimeArray,
+        RawInverseReducedIonMobilityArray,
+        DeconvolutedIonMobilityArray,
+        DeconvolutedDriftTimeArray,
+        DeconvolutedInverseReducedIonMobilityArray,
+        BaselineArray,
+        ResolutionArray,
+        PressureArray,
+        TemperatureArray,
+        FlowRateArray,
+        NonStandardDataArray,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BinaryDataArrayType {
+        Unknown,
+        Float64,
+        Float32,
+        Int64,
+        Int32,
+        ASCII,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BinaryCompressionType {
+        NoCompression,
+        Zlib,
+        NumpressLinear,
+        NumpressSLOF,
+        NumpressPIC,
+        NumpressLinearZlib,
+        NumpressSLOFZlib,
+        NumpressPICZlib,
+        LinearPrediction,
+        DeltaPrediction,
+        Decoded,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ScanPolarity {
+        Unknown,
+        Positive,
+        Negative,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SignalContinuity {
+        Unknown,
+        Centroid,
+        Profile,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PeakFitType {
+        Quadratic,
+        Apex,
+        Lorentzian,
+    }
+
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct CURIE {
         pub controlled_vocabulary: ControlledVocabulary,
         pub accession: u32,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ToleranceUnit {
+        PPM,
+        Da,
+    }
+
+    /// A peak or data point matched by a tolerance search
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct PeakMatch {
+        pub index: usize,
+        pub mz: f64,
+        pub intensity: f32,
+    }
+
+    /// A dissociation method with its human-readable name
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ActivationMethod {
+        pub curie: CURIE,
+        pub name: String,
+        pub is_supplemental: bool,
+    }
+
+    /// The m/z range covered by a scan
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct ScanWindow {
+        pub lower_bound: f32,
+        pub upper_bound: f32,
+    }
+
+    /// An ion mobility frame's signal summed onto a regular ion mobility by m/z grid,
+    /// stored row-major with one row per ion mobility bin
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct BinnedFrame {
+        pub mz_start: f64,
+        pub mz_bin_width: f64,
+        pub mz_bin_count: usize,
+        pub ion_mobility_start: f64,
+        pub ion_mobility_bin_width: f64,
+        pub ion_mobility_bin_count: usize,
+        pub intensity: Vec<f32>,
+    }
+
+    /// A feature traced across the ion mobility dimension of a frame
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct IonMobilityFeature {
+        pub mz: f64,
+        pub apex_ion_mobility: f64,
+        pub intensity: f32,
+        pub ion_mobility: Vec<f64>,
+        pub mzs: Vec<f64>,
+        pub intensities: Vec<f32>,
+    }
+
+    /// Intensity as a function of ion mobility for a single target m/z
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Mobilogram {
+        pub target_mz: f64,
+        pub ion_mobility: Vec<f64>,
+        pub intensity: Vec<f32>,
+    }
+
+    /// Intensity for a single target m/z over retention time and binned ion mobility,
+    /// stored row-major with one row per frame
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MobilityMap {
+        pub target_mz: f64,
+        pub time: Vec<f64>,
+        pub ion_mobility_start: f64,
+        pub ion_mobility_bin_width: f64,
+        pub ion_mobility_bin_count: usize,
+        pub intensity: Vec<f32>,
+    }
+
+    /// How an isolation window's bounds were established from the source file
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IsolationWindowState {
+        Unknown,
+        Offset,
+        Explicit,
+        Complete,
+    }
+
+    /// A distinct precursor isolation window of a DIA acquisition
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct DIAWindow {
+        pub target: f32,
+        pub lower_bound: f32,
+        pub upper_bound: f32,
+        pub state: IsolationWindowState,
+    }
+
+    /// The distinct isolation windows of a DIA acquisition ordered by m/z, with the
+    /// number of windows acquired in a typical cycle
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DIAWindowScheme {
+        pub windows: Vec<DIAWindow>,
+        pub cycle_length: usize,
+        pub cycle_count: usize,
+        pub is_overlapping: bool,
+        pub is_staggered: bool,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HasIonMobility {
+        None,
+        Point,
+        Dimension,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IonMobilityType {
+        InverseReducedIonMobility,
+        DriftTime,
+        CompensationVoltage,
+        Unknown,
+    }
+
+    /// An extracted ion chromatogram for a single target m/z
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct IonTrace {
+        pub target_mz: f64,
+        pub time: Vec<f64>,
+        pub intensity: Vec<f32>,
+    }
+
     extern "Rust" {
         pub type Param;
 
@@ -720,8 +3730,29 @@ pub(crate) mod ffi {
         pub fn contains(&self, point: f32) -> bool;
         pub fn is_empty(&self) -> bool;
         pub fn target(&self) -> f32;
+        /// A window with only a target m/z has no bounds. Its bounds are then the target,
+        /// and its offsets and width are 0.
+        pub fn has_bounds(&self) -> bool;
         pub fn lower_bound(&self) -> f32;
         pub fn upper_bound(&self) -> f32;
+        pub fn lower_offset(&self) -> f32;
+        pub fn upper_offset(&self) -> f32;
+        pub fn width(&self) -> f32;
+        pub fn state(&self) -> IsolationWindowState;
+    }
+
+    extern "Rust" {
+        pub type SelectedIon;
+
+        pub fn mz(&self) -> f64;
+        pub fn charge(&self, value: &mut i32) -> bool;
+        pub fn neutral_mass(&self) -> f64;
+        pub fn intensity(&self) -> f32;
+        pub fn ion_mobility(&self, value: &mut f64) -> bool;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
     }
 
     extern "Rust" {
@@ -730,13 +3761,37 @@ pub(crate) mod ffi {
         pub fn selected_mz(&self, value: &mut f64) -> bool;
         pub fn selected_charge(&self, value: &mut i32) -> bool;
         pub fn selected_ion_mobility(&self, value: &mut f64) -> bool;
+        pub fn ion_count(&self) -> usize;
+        pub fn ion_at(&self, index: usize) -> Result<Box<SelectedIon>>;
         pub fn isolation_window(&self) -> Result<Box<IsolationWindow>>;
+        pub fn get_isolation_window(&self) -> Box<IsolationWindow>;
+        pub fn precursor_id(&self, mut out: Pin<&mut CxxString>) -> bool;
+        pub fn product_id(&self, mut out: Pin<&mut CxxString>) -> bool;
         pub fn activation_energy(&self, value: &mut f32) -> bool;
+        pub unsafe fn activation<'a>(&'a self) -> Box<Activation<'a>>;
         pub fn activation_method_is_combined(&self) -> bool;
         pub fn activation_methods(&self) -> Vec<CURIE>;
         pub fn activation_method(&self, value: &mut CURIE) -> bool;
     }
 
+    extern "Rust" {
+        pub type Activation<'a>;
+
+        pub fn has_energy(&self) -> bool;
+        pub fn energy(&self) -> f32;
+        /// The mzML reader keeps only the last non-supplemental energy of a precursor, so
+        /// the steps of a stepped collision energy read from mzML collapse to one value.
+        pub fn energies(&self) -> Vec<f32>;
+        pub fn supplemental_energy(&self, value: &mut f32) -> bool;
+        pub fn methods(&self) -> Vec<ActivationMethod>;
+        pub fn is_combined(&self) -> bool;
+        pub fn is_supplemental(&self) -> bool;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
     extern "Rust" {
         pub type ScanEvent<'a>;
 
@@ -748,6 +3803,7 @@ pub(crate) mod ffi {
 
         pub fn scan_configuration(&self, mut out: Pin<&mut CxxString>) -> bool;
         pub fn filter_string(&self, mut out: Pin<&mut CxxString>) -> bool;
+        pub fn scan_windows(&self) -> Vec<ScanWindow>;
 
         pub fn param(&self, index: usize) -> Result<Box<Param>>;
         pub fn params(&self) -> Vec<Param>;
@@ -778,6 +3834,16 @@ pub(crate) mod ffi {
             mut mzs_container: Pin<&mut CxxVector<f64>>,
             mut intensities_container: Pin<&mut CxxVector<f32>>,
         );
+        /// The views borrow the decoded arrays without converting them, so they fail with
+        /// a `ProcessingError` unless m/z values are stored as 64-bit and intensities as
+        /// 32-bit floats. Use `signal_into` to copy other layouts.
+        pub fn mzs_view(&mut self) -> Result<&[f64]>;
+        pub fn intensities_view(&mut self) -> Result<&[f32]>;
+
+        pub fn array_count(&self) -> usize;
+        pub unsafe fn array<'a>(&'a self, index: usize) -> Result<Box<DataArray<'a>>>;
+        pub unsafe fn get_array<'a>(&'a self, array_type: ArrayType) -> Result<Box<DataArray<'a>>>;
+        pub unsafe fn get_array_by_name<'a>(&'a self, name: &str) -> Result<Box<DataArray<'a>>>;
 
         pub fn id(&self) -> &str;
         pub fn index(&self) -> usize;
@@ -785,13 +3851,71 @@ pub(crate) mod ffi {
         pub fn ms_level(&self) -> u8;
         pub fn is_profile(&self) -> bool;
         pub unsafe fn precursor<'a>(&'a self) -> Result<Box<Precursor<'a>>>;
+        /// mzdata keeps a single precursor and its mzML reader merges every `<precursor>`
+        /// element into it, so this is 0 or 1 and chimeric, MSX or SPS-MS3 precursors are
+        /// not available separately.
+        pub fn precursor_count(&self) -> usize;
+        pub fn dia_window_index(&self, scheme: &DIAWindowScheme, value: &mut usize) -> bool;
+        pub unsafe fn precursor_at<'a>(&'a self, index: usize) -> Result<Box<Precursor<'a>>>;
         pub unsafe fn acquisition<'a>(&'a self) -> Box<Acquisition<'a>>;
+        pub fn tic(&self) -> f32;
+        pub fn base_peak_mz(&self) -> f64;
+        pub fn base_peak_intensity(&self) -> f32;
+        pub fn lowest_mz(&self) -> f64;
+        pub fn highest_mz(&self) -> f64;
+        pub fn peak_count(&self) -> usize;
+
+        pub fn pick_peaks(&mut self, signal_to_noise_threshold: f32) -> Result<()>;
+        pub fn pick_peaks_with_fit(
+            &mut self,
+            signal_to_noise_threshold: f32,
+            fit_type: PeakFitType,
+        ) -> Result<()>;
+        pub fn centroid_mzs_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> bool;
+        pub fn centroid_intensities_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> bool;
+        pub fn find_peak(
+            &self,
+            mz: f64,
+            tolerance: f64,
+            unit: ToleranceUnit,
+            value: &mut PeakMatch,
+        ) -> bool;
+        pub fn find_all_peaks(
+            &self,
+            mz: f64,
+            tolerance: f64,
+            unit: ToleranceUnit,
+        ) -> Vec<PeakMatch>;
+
+        pub fn polarity(&self) -> ScanPolarity;
+        pub fn signal_continuity(&self) -> SignalContinuity;
+        pub fn set_id(&mut self, id: &str);
+        pub fn set_index(&mut self, index: usize);
+        pub fn set_ms_level(&mut self, ms_level: u8);
+        pub fn set_start_time(&mut self, start_time: f64);
+        pub fn set_polarity(&mut self, polarity: ScanPolarity);
+        pub fn set_signal_continuity(&mut self, signal_continuity: SignalContinuity);
+        pub fn set_mzs(&mut self, mzs: &[f64]) -> Result<()>;
+        pub fn set_intensities(&mut self, intensities: &[f32]) -> Result<()>;
+        /// Replace both arrays at once. `set_mzs` and `set_intensities` fail when the
+        /// other array is present with a different length.
+        pub fn set_signal(&mut self, mzs: &[f64], intensities: &[f32]) -> Result<()>;
+        pub fn set_precursor(&mut self, mz: f64, charge: i32);
+        pub fn set_isolation_window(&mut self, target: f32, lower_offset: f32, upper_offset: f32);
+        pub fn set_activation(&mut self, method: &CURIE, energy: f32) -> Result<()>;
+        pub fn add_param(&mut self, param: &Param);
+        pub fn add_user_param(&mut self, name: &str, value: &str);
+        pub fn add_cv_param(&mut self, curie: &CURIE, name: &str, value: &str);
 
         pub fn param(&self, index: usize) -> Result<Box<Param>>;
         pub fn params(&self) -> Vec<Param>;
         pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
     }
 
+    extern "Rust" {
+        pub fn new_spectrum() -> Box<Spectrum>;
+    }
+
     extern "Rust" {
         pub type IonMobilityFrame;
 
@@ -801,8 +3925,25 @@ pub(crate) mod ffi {
         pub fn ms_level(&self) -> u8;
         pub fn is_profile(&self) -> bool;
         pub unsafe fn precursor<'a>(&'a self) -> Result<Box<Precursor<'a>>>;
+        /// mzdata keeps a single precursor and its mzML reader merges every `<precursor>`
+        /// element into it, so this is 0 or 1 and chimeric, MSX or SPS-MS3 precursors are
+        /// not available separately.
+        pub fn precursor_count(&self) -> usize;
+        pub unsafe fn precursor_at<'a>(&'a self, index: usize) -> Result<Box<Precursor<'a>>>;
+        pub unsafe fn acquisition<'a>(&'a self) -> Box<Acquisition<'a>>;
+        pub fn tic(&self) -> f32;
+        pub fn base_peak_mz(&self) -> f64;
+        pub fn base_peak_intensity(&self) -> f32;
+        pub fn lowest_mz(&self) -> f64;
+        pub fn highest_mz(&self) -> f64;
+        pub fn peak_count(&self) -> usize;
 
         pub fn ion_mobility_dimension(&self, mut out: Pin<&mut CxxVector<f64>>) -> bool;
+        pub fn ion_mobility_range(&self, low: &mut f64, high: &mut f64) -> bool;
+        pub fn ion_mobility_type(&self) -> IonMobilityType;
+        pub fn ion_mobility_unit_name(&self) -> &'static str;
+        pub fn ion_mobility_unit_curie(&self, value: &mut CURIE) -> bool;
+        pub fn accumulation_time(&self, value: &mut f64) -> bool;
 
         pub fn param(&self, index: usize) -> Result<Box<Param>>;
         pub fn params(&self) -> Vec<Param>;
@@ -815,26 +3956,313 @@ pub(crate) mod ffi {
             mut intensities_container: Pin<&mut CxxVector<f32>>,
             ion_mobility: &mut f64,
         );
+        pub fn signal_into(
+            &self,
+            mut ion_mobility_container: Pin<&mut CxxVector<f64>>,
+            mut mzs_container: Pin<&mut CxxVector<f64>>,
+            mut intensities_container: Pin<&mut CxxVector<f32>>,
+            mut offsets: Pin<&mut CxxVector<usize>>,
+        ) -> Result<()>;
+        /// Fails with a `ProcessingError` when the grid would exceed 2^28 cells.
+        pub fn binned_signal(
+            &self,
+            mz_bin_width: f64,
+            ion_mobility_bin_width: f64,
+        ) -> Result<BinnedFrame>;
+        /// Fails with a `ProcessingError` when a profile frame's m/z grid would exceed
+        /// 2^28 cells.
+        pub fn to_spectrum(
+            &self,
+            ion_mobility_low: f64,
+            ion_mobility_high: f64,
+            mz_tolerance: f64,
+        ) -> Result<Box<Spectrum>>;
+        pub fn extract_features(
+            &mut self,
+            mz_tolerance_ppm: f64,
+            min_length: usize,
+            max_gap: f64,
+        ) -> Result<Vec<IonMobilityFeature>>;
+        pub fn extract_mobilogram(&self, mz: f64, tolerance_ppm: f64) -> Result<Mobilogram>;
+        pub fn ion_mobility_dimension_view(&self) -> &[f64];
+        /// Like `Spectrum::mzs_view`, these fail instead of converting arrays stored as
+        /// other types.
+        pub fn mzs_view_at(&mut self, ion_mobility_index: usize) -> Result<&[f64]>;
+        pub fn intensities_view_at(&mut self, ion_mobility_index: usize) -> Result<&[f32]>;
+    }
+
+    extern "Rust" {
+        pub type InstrumentComponent<'a>;
+
+        pub fn component_type(&self) -> ComponentType;
+        pub fn order(&self) -> u8;
+        pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type InstrumentConfiguration<'a>;
+
+        pub fn id(&self) -> u32;
+        pub unsafe fn software_reference<'a>(&'a self) -> &'a str;
+        pub fn len(&self) -> usize;
+        pub fn is_empty(&self) -> bool;
+        pub unsafe fn component<'a>(&'a self, index: usize)
+            -> Result<Box<InstrumentComponent<'a>>>;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type Software<'a>;
+
+        pub unsafe fn id<'a>(&'a self) -> &'a str;
+        pub unsafe fn version<'a>(&'a self) -> &'a str;
+        pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool;
+        pub fn is_analysis(&self) -> bool;
+        pub fn is_data_processing(&self) -> bool;
+        pub fn is_acquisition(&self) -> bool;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type ProcessingMethod<'a>;
+
+        pub fn order(&self) -> i8;
+        pub unsafe fn software_reference<'a>(&'a self) -> &'a str;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type DataProcessing<'a>;
+
+        pub unsafe fn id<'a>(&'a self) -> &'a str;
+        pub fn len(&self) -> usize;
+        pub fn is_empty(&self) -> bool;
+        pub unsafe fn method<'a>(&'a self, index: usize) -> Result<Box<ProcessingMethod<'a>>>;
+    }
+
+    extern "Rust" {
+        pub type SourceFile<'a>;
+
+        pub unsafe fn id<'a>(&'a self) -> &'a str;
+        pub unsafe fn name<'a>(&'a self) -> &'a str;
+        pub unsafe fn location<'a>(&'a self) -> &'a str;
+        pub fn file_format(&self) -> Result<Box<Param>>;
+        pub fn id_format(&self) -> Result<Box<Param>>;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type Sample<'a>;
+
+        pub unsafe fn id<'a>(&'a self) -> &'a str;
+        pub fn name(&self, mut out: Pin<&mut CxxString>) -> bool;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type RunMetadata;
+
+        pub fn run_id(&self, mut out: Pin<&mut CxxString>) -> bool;
+        pub fn start_timestamp(&self, mut out: Pin<&mut CxxString>) -> bool;
+        pub fn default_instrument_configuration_id(&self, value: &mut u32) -> bool;
+        pub fn spectrum_count_hint(&self, value: &mut u64) -> bool;
+        pub fn file_contents(&self) -> Vec<Param>;
+
+        pub fn instrument_configuration_ids(&self) -> Vec<u32>;
+        pub unsafe fn instrument_configuration<'a>(
+            &'a self,
+            id: u32,
+        ) -> Result<Box<InstrumentConfiguration<'a>>>;
+
+        pub fn software_count(&self) -> usize;
+        pub unsafe fn software<'a>(&'a self, index: usize) -> Result<Box<Software<'a>>>;
+
+        pub fn data_processing_count(&self) -> usize;
+        pub unsafe fn data_processing<'a>(
+            &'a self,
+            index: usize,
+        ) -> Result<Box<DataProcessing<'a>>>;
+
+        pub fn source_file_count(&self) -> usize;
+        pub unsafe fn source_file<'a>(&'a self, index: usize) -> Result<Box<SourceFile<'a>>>;
+
+        pub fn sample_count(&self) -> usize;
+        pub unsafe fn sample<'a>(&'a self, index: usize) -> Result<Box<Sample<'a>>>;
+    }
+
+    extern "Rust" {
+        /// mzdata does not keep a chromatogram's `<product>` element, so the product
+        /// isolation window of SRM/MRM chromatograms is not available
+        pub type Chromatogram;
+
+        pub fn id(&self) -> &str;
+        pub fn index(&self) -> usize;
+        pub fn chromatogram_type(&self) -> ChromatogramType;
+        pub fn ms_level(&self, value: &mut u8) -> bool;
+        pub fn is_aggregate(&self) -> bool;
+        pub fn time_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> bool;
+        pub fn intensity_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> bool;
+        pub unsafe fn precursor<'a>(&'a self) -> Result<Box<Precursor<'a>>>;
+
+        pub fn array_count(&self) -> usize;
+        pub unsafe fn array<'a>(&'a self, index: usize) -> Result<Box<DataArray<'a>>>;
+        pub unsafe fn get_array<'a>(&'a self, array_type: ArrayType) -> Result<Box<DataArray<'a>>>;
+        pub unsafe fn get_array_by_name<'a>(&'a self, name: &str) -> Result<Box<DataArray<'a>>>;
+
+        pub fn param(&self, index: usize) -> Result<Box<Param>>;
+        pub fn params(&self) -> Vec<Param>;
+        pub fn get_param_by_curie(&self, curie: &CURIE) -> Result<Box<Param>>;
+    }
+
+    extern "Rust" {
+        pub type DataArray<'a>;
+
+        pub fn array_type(&self) -> ArrayType;
+        pub fn name(&self) -> String;
+        pub fn unit_name(&self) -> &'static str;
+        pub fn unit_curie(&self, value: &mut CURIE) -> bool;
+        pub fn dtype(&self) -> BinaryDataArrayType;
+        pub fn compression(&self) -> BinaryCompressionType;
+        pub fn len(&self) -> Result<usize>;
+        pub fn is_empty(&self) -> Result<bool>;
+        pub fn f32_into(&self, mut container: Pin<&mut CxxVector<f32>>) -> Result<()>;
+        pub fn f64_into(&self, mut container: Pin<&mut CxxVector<f64>>) -> Result<()>;
+        pub fn i32_into(&self, mut container: Pin<&mut CxxVector<i32>>) -> Result<()>;
+        pub fn i64_into(&self, mut container: Pin<&mut CxxVector<i64>>) -> Result<()>;
+    }
+
+    extern "Rust" {
+        pub type ChromatogramIterator<'a>;
+
+        pub fn next(&mut self) -> Result<Box<Chromatogram>>;
+    }
+
+    extern "Rust" {
+        pub type DIACycleIterator<'a>;
+
+        pub fn next(&mut self) -> Result<Box<DIACycle>>;
+    }
+
+    extern "Rust" {
+        pub type DIACycle;
+
+        pub fn has_ms1(&self) -> bool;
+        pub fn ms1(&self) -> Result<Box<Spectrum>>;
+        pub fn msn_count(&self) -> usize;
+        pub fn msn_at(&self, index: usize) -> Result<Box<Spectrum>>;
     }
 
     extern "Rust" {
         pub type MZReader;
 
         pub fn open(path: &str) -> Result<Box<MZReader>>;
+        /// Reads from `rt_start` and resets `reader` to the first spectrum afterwards, even
+        /// on error, so iteration resumes from the start rather than from the prior position.
+        pub fn extract_xics(
+            reader: &mut MZReader,
+            targets: &[f64],
+            tolerance_ppm: f64,
+            rt_start: f64,
+            rt_end: f64,
+            ms_level: u8,
+        ) -> Result<Vec<IonTrace>>;
+        pub fn into_frame_reader(reader: Box<MZReader>) -> Result<Box<IMMZReader>>;
 
         pub fn size(&self) -> usize;
+        pub fn has_ion_mobility(&mut self) -> HasIonMobility;
+        pub fn has_ion_mobility_dimension(&mut self) -> bool;
         pub fn next(&mut self) -> Result<Box<Spectrum>>;
+        pub fn has_next(&mut self) -> Result<bool>;
         pub fn get_by_index(&mut self, index: usize) -> Result<Box<Spectrum>>;
+        pub fn get_by_id(&mut self, id: &str) -> Result<Box<Spectrum>>;
+        pub fn get_by_time(&mut self, time: f64) -> Result<Box<Spectrum>>;
+        pub fn get_parent(&mut self, spectrum: &Spectrum) -> Result<Box<Spectrum>>;
+
+        pub fn start_from_index(&mut self, index: usize) -> Result<()>;
+        pub fn start_from_id(&mut self, id: &str) -> Result<()>;
+        pub fn start_from_time(&mut self, time: f64) -> Result<()>;
+        pub fn reset(&mut self);
+        pub fn metadata(&self) -> Box<RunMetadata>;
+
+        /// Uses the chromatogram index when the format has one, otherwise reads the
+        /// metadata of every chromatogram
+        pub fn chromatogram_count(&mut self) -> usize;
+        pub fn get_chromatogram_by_index(&mut self, index: usize) -> Result<Box<Chromatogram>>;
+        pub fn get_chromatogram_by_id(&mut self, id: &str) -> Result<Box<Chromatogram>>;
+        pub unsafe fn iter_chromatograms<'a>(&'a mut self) -> Box<ChromatogramIterator<'a>>;
+        /// Reads the whole file and resets the reader to the first spectrum afterwards,
+        /// so iteration resumes from the start rather than from the prior position.
+        /// Windows recorded with only a target m/z are keyed by that target.
+        pub fn dia_window_scheme(&mut self) -> Result<DIAWindowScheme>;
+        pub unsafe fn iter_cycles<'a>(&'a mut self) -> Box<DIACycleIterator<'a>>;
     }
 
     extern "Rust" {
         pub type IMMZReader;
 
         pub fn open_im(path: &str) -> Result<Box<IMMZReader>>;
+        /// Reads from `rt_start` and resets `reader` to the first frame afterwards, even on
+        /// error, so iteration resumes from the start rather than from the prior position.
+        pub fn extract_mobility_map(
+            reader: &mut IMMZReader,
+            mz: f64,
+            tolerance_ppm: f64,
+            rt_start: f64,
+            rt_end: f64,
+            ms_level: u8,
+            ion_mobility_bin_width: f64,
+        ) -> Result<MobilityMap>;
 
         pub fn next(&mut self) -> Result<Box<IonMobilityFrame>>;
+        pub fn has_next(&mut self) -> Result<bool>;
         pub fn get_by_index(&mut self, index: usize) -> Result<Box<IonMobilityFrame>>;
+        pub fn get_by_id(&mut self, id: &str) -> Result<Box<IonMobilityFrame>>;
+        pub fn get_by_time(&mut self, time: f64) -> Result<Box<IonMobilityFrame>>;
+
+        pub fn start_from_index(&mut self, index: usize) -> Result<()>;
+        pub fn start_from_id(&mut self, id: &str) -> Result<()>;
+        pub fn start_from_time(&mut self, time: f64) -> Result<()>;
+        pub fn reset(&mut self);
+        pub fn metadata(&self) -> Box<RunMetadata>;
         pub fn size(&self) -> usize;
 
     }
+
+    extern "Rust" {
+        /// The kind of the last error raised on this thread, as a `rust::Error` only
+        /// carries the message
+        pub fn last_error_kind() -> ErrorKind;
+    }
+
+    extern "Rust" {
+        pub type MZWriter;
+
+        pub fn create(path: &str, format: MassSpectrometryFormat) -> Result<Box<MZWriter>>;
+
+        /// mzML files otherwise declare `count="0"` on their spectrum list. Call this before
+        /// the first `write_spectrum`, after which it fails. MGF files ignore it.
+        pub fn set_spectrum_count(&mut self, count: u64) -> Result<()>;
+        pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<()>;
+        pub fn flush(&mut self) -> Result<()>;
+        pub fn close(&mut self) -> Result<()>;
+    }
 }
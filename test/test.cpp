@@ -1,10 +1,64 @@
 #include<memory>
 #include<iostream>
+#include<optional>
 #include "cxx.h"
 #include "lib.rs.h"
+#include "shim.h"
+
+// Precursor checks for an MSn spectrum read from `reader`
+int check_precursor(mzdata_cpp::MZReader& reader, mzdata_cpp::Spectrum& spec) {
+    std::cout << "Checking precursor of " << spec.id() << std::endl;
+    auto precursor = spec.precursor();
+    std::cout << "Precursor has " << precursor->ion_count() << " selected ions" << std::endl;
+    auto activation = precursor->activation();
+    for (const auto& method : activation->methods()) {
+        std::cout << "Activated by " << std::string(method.name) << " at " << activation->energy() << std::endl;
+    }
+
+    double prec_mz;
+    if (precursor->selected_mz(prec_mz)) {
+        std::printf("Selected ion m/z: %f\n", prec_mz);
+    }
+    try {
+        auto iso = mzdata_cpp::checked([&] { return precursor->isolation_window(); });
+        if (!iso->contains(iso->target())) {
+            std::cerr << "Isolation window does not contain its target" << std::endl;
+            return 1;
+        }
+        if (!iso->has_bounds() && iso->width() != 0.0) {
+            std::cerr << "Target-only isolation window has a width" << std::endl;
+            return 1;
+        }
+        std::cout << "Isolation window " << iso->lower_offset() << " below and "
+                  << iso->upper_offset() << " above the target" << std::endl;
+    } catch (const mzdata_cpp::NotFoundError& err) {
+        std::cout << err.what() << std::endl;
+    }
+    try {
+        auto parent = mzdata_cpp::checked([&] { return reader.get_parent(spec); });
+        std::cout << "Parent of " << spec.id() << " is " << parent->id() << std::endl;
+    } catch (const mzdata_cpp::NotFoundError& err) {
+        std::cout << err.what() << std::endl;
+    }
+    return 0;
+}
+
+int main(int argc, char** argv) {
+    auto reader = mzdata_cpp::open(argc > 1 ? argv[1] : "batching_test.mzML");
+    try {
+        reader->get_by_index(reader->size());
+    } catch (const rust::Error& err) {
+        if (mzdata_cpp::last_error_kind() != mzdata_cpp::ErrorKind::IndexOutOfRange) {
+            std::cerr << "Unexpected error kind for: " << err.what() << std::endl;
+            return 1;
+        }
+    }
+    try {
+        mzdata_cpp::checked([&] { return reader->get_by_id("not-a-spectrum"); });
+    } catch (const mzdata_cpp::NotFoundError& err) {
+        std::cout << err.what() << std::endl;
+    }
 
-int main() {
-    auto reader = mzdata_cpp::open("batching_test.mzML");
     std::cout << "Reading spectrum?" << std::endl;
     auto spec = reader->next();
 
@@ -15,13 +69,114 @@ int main() {
     std::vector<float> intensities;
     spec->signal_into(mzs, intensities);
     std::cout << "Read " << mzs.size() << " data points" << std::endl;
-    auto precursor = spec->precursor();
 
-    double prec_mz;
-    if (precursor->selected_mz(prec_mz)) {
-        std::printf("Selected ion m/z: %f", prec_mz);
+    auto dtype_of = [&](mzdata_cpp::ArrayType array_type) {
+        return spec->get_array(array_type)->dtype();
+    };
+    auto mz_dtype = dtype_of(mzdata_cpp::ArrayType::MZArray);
+    auto intensity_dtype = dtype_of(mzdata_cpp::ArrayType::IntensityArray);
+    try {
+        rust::Slice<const double> mz_view = spec->mzs_view();
+        rust::Slice<const float> intensity_view = spec->intensities_view();
+        std::cout << "Viewing " << mz_view.size() << " m/z and " << intensity_view.size() << " intensity values" << std::endl;
+    } catch (const rust::Error& err) {
+        if (mzdata_cpp::last_error_kind() != mzdata_cpp::ErrorKind::ProcessingError) {
+            std::cerr << "Unexpected error kind for: " << err.what() << std::endl;
+            return 1;
+        }
+        std::cout << err.what() << std::endl;
+    }
+    if (dtype_of(mzdata_cpp::ArrayType::MZArray) != mz_dtype
+        || dtype_of(mzdata_cpp::ArrayType::IntensityArray) != intensity_dtype) {
+        std::cerr << "Viewing the arrays converted them" << std::endl;
+        return 1;
+    }
+
+    mzdata_cpp::PeakMatch match;
+    if (spec->find_peak(445.12, 10.0, mzdata_cpp::ToleranceUnit::PPM, match)) {
+        std::cout << "Found peak " << match.mz << " at index " << match.index << std::endl;
+    }
+
+    for (size_t i = 0; i < spec->array_count(); i++) {
+        auto array = spec->array(i);
+        std::cout << std::string(array->name()) << " (" << array->unit_name() << "): " << array->len() << std::endl;
+    }
+    auto mz_array = spec->get_array_by_name("m/z array");
+    std::cout << "Found " << std::string(mz_array->name()) << " by name" << std::endl;
+    if (spec->is_profile()) {
+        spec->pick_peaks(1.0);
+        std::vector<double> centroid_mzs;
+        spec->centroid_mzs_into(centroid_mzs);
+        std::cout << "Picked " << centroid_mzs.size() << " peaks" << std::endl;
+    }
+
+    std::optional<rust::Box<mzdata_cpp::Spectrum>> msn;
+    for (size_t i = 0; i < reader->size() && !msn; i++) {
+        auto candidate = reader->get_by_index(i);
+        if (candidate->ms_level() > 1) {
+            msn = std::move(candidate);
+        }
+    }
+    if (msn) {
+        if (int status = check_precursor(*reader, **msn)) {
+            return status;
+        }
+    } else {
+        std::cout << "No MSn spectrum, skipping precursor checks" << std::endl;
+    }
+
+    auto writer = mzdata_cpp::create("batching_test.out.mzML", mzdata_cpp::MassSpectrometryFormat::MzML);
+    writer->set_spectrum_count(2);
+    writer->write_spectrum(*spec);
+    try {
+        writer->set_spectrum_count(3);
+        std::cerr << "Spectrum count was changed after the header was written" << std::endl;
+        return 1;
+    } catch (const rust::Error &) {
+    }
+
+    auto built = mzdata_cpp::new_spectrum();
+    built->set_id("scan=1000");
+    built->set_ms_level(2);
+    built->set_start_time(12.5);
+    built->set_polarity(mzdata_cpp::ScanPolarity::Positive);
+    built->set_signal_continuity(mzdata_cpp::SignalContinuity::Centroid);
+    std::vector<double> built_mzs = {100.0, 200.0, 300.0};
+    std::vector<float> built_intensities = {10.0, 20.0, 30.0};
+    built->set_mzs(rust::Slice<const double>(built_mzs.data(), built_mzs.size()));
+    built->set_intensities(rust::Slice<const float>(built_intensities.data(), built_intensities.size()));
+    std::vector<double> short_mzs = {100.0};
+    try {
+        built->set_mzs(rust::Slice<const double>(short_mzs.data(), short_mzs.size()));
+        std::cerr << "Mismatched m/z array was accepted" << std::endl;
+        return 1;
+    } catch (const rust::Error &) {
+    }
+    built->set_precursor(450.25, 2);
+    built->set_isolation_window(450.25, 1.0, 1.0);
+    built->set_activation(mzdata_cpp::CURIE{mzdata_cpp::ControlledVocabulary::MS, 1000133}, 30.0);
+    writer->write_spectrum(*built);
+    writer->close();
+
+    std::vector<double> targets = {445.12, 519.14};
+    auto traces = mzdata_cpp::extract_xics(*reader, rust::Slice<const double>(targets.data(), targets.size()), 10.0, 0.0, 60.0, 1);
+    for (const auto& trace : traces) {
+        std::cout << "XIC " << trace.target_mz << ": " << trace.time.size() << " points" << std::endl;
+    }
+
+    // extract_xics leaves the reader at the first spectrum
+    size_t n_spectra = 0;
+    for (auto& spec : *reader) {
+        n_spectra += 1;
     }
-    auto iso = precursor->isolation_window();
+    std::cout << "Read " << n_spectra << " spectra" << std::endl;
+
+    auto scheme = reader->dia_window_scheme();
+    std::cout << "Found " << scheme.windows.size() << " isolation windows over "
+              << scheme.cycle_count << " cycles" << std::endl;
+    auto cycles = reader->iter_cycles();
+    auto cycle = cycles->next();
+    std::cout << "First cycle has " << cycle->msn_count() << " MSn spectra" << std::endl;
     std::cout << "Done" << std::endl;
     return 0;
 }
\ No newline at end of file
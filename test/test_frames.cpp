@@ -0,0 +1,76 @@
+#include<memory>
+#include<iostream>
+#include<cmath>
+#include "cxx.h"
+#include "lib.rs.h"
+#include "shim.h"
+
+int main(int argc, char** argv) {
+    auto reader = mzdata_cpp::open(argc > 1 ? argv[1] : "batching_test.mzML");
+    if (reader->has_ion_mobility() != mzdata_cpp::HasIonMobility::Dimension) {
+        std::cerr << "Expected a file with an ion mobility dimension" << std::endl;
+        return 1;
+    }
+    auto frames = mzdata_cpp::into_frame_reader(std::move(reader));
+    auto frame = frames->next();
+    auto binned = frame->binned_signal(0.01, 0.01);
+    std::cout << "Binned frame into " << binned.mz_bin_count << " x "
+              << binned.ion_mobility_bin_count << " cells" << std::endl;
+    try {
+        frame->binned_signal(1e-9, 1e-9);
+        std::cerr << "Oversized grid was accepted" << std::endl;
+        return 1;
+    } catch (const rust::Error& err) {
+        if (mzdata_cpp::last_error_kind() != mzdata_cpp::ErrorKind::ProcessingError) {
+            std::cerr << "Unexpected error kind for: " << err.what() << std::endl;
+            return 1;
+        }
+    }
+    double im_low = 0.0, im_high = 0.0;
+    frame->ion_mobility_range(im_low, im_high);
+    auto merged = frame->to_spectrum(im_low, im_high, 0.01);
+    std::vector<double> frame_ims, frame_mzs, merged_mzs;
+    std::vector<float> frame_intensities, merged_intensities;
+    std::vector<size_t> offsets;
+    frame->signal_into(frame_ims, frame_mzs, frame_intensities, offsets);
+    merged->signal_into(merged_mzs, merged_intensities);
+    double frame_total = 0.0, merged_total = 0.0;
+    for (auto intensity : frame_intensities) frame_total += intensity;
+    for (auto intensity : merged_intensities) merged_total += intensity;
+    if (std::abs(frame_total - merged_total) > 1e-3 * frame_total) {
+        std::cerr << "Merged spectrum lost intensity" << std::endl;
+        return 1;
+    }
+    if (!frame->is_profile()) {
+        for (size_t i = 1; i < merged_mzs.size(); i++) {
+            if (merged_mzs[i] - merged_mzs[i - 1] <= 0.01) {
+                std::cerr << "Centroids closer than the tolerance were not merged" << std::endl;
+                return 1;
+            }
+        }
+    }
+    std::cout << "Merged frame into " << merged_mzs.size() << " points" << std::endl;
+    if (!frame->is_profile()) {
+        auto features = frame->extract_features(20.0, 2, 0.25);
+        std::cout << "Extracted " << features.size() << " ion mobility features" << std::endl;
+    }
+    if (!frame_mzs.empty()) {
+        auto mobilogram = frame->extract_mobilogram(frame_mzs.front(), 20.0);
+        std::cout << "Mobilogram has " << mobilogram.ion_mobility.size() << " points" << std::endl;
+    }
+
+    auto map = mzdata_cpp::extract_mobility_map(*frames, frame_mzs.empty() ? 0.0 : frame_mzs.front(), 20.0, 0.0, 1e6, frame->ms_level(), 0.01);
+    std::cout << "Mobility map has " << map.time.size() << " x " << map.ion_mobility_bin_count << " cells" << std::endl;
+
+    // extract_mobility_map leaves the reader at the first frame
+    size_t n_frames = 0;
+    for (auto& frame : *frames) {
+        n_frames += 1;
+    }
+    if (n_frames != frames->size()) {
+        std::cerr << "Read " << n_frames << " of " << frames->size() << " frames" << std::endl;
+        return 1;
+    }
+    std::cout << "Done" << std::endl;
+    return 0;
+}